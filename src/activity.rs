@@ -0,0 +1,103 @@
+use std::collections::VecDeque;
+
+/// Kind of a competition event shown in the activity feed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ActivityKind {
+    Solve,
+    /// First solve of a challenge.
+    FirstBlood,
+    NewTeam,
+    Announcement,
+    /// Team renamed or removed by an admin.
+    AdminChange,
+}
+
+impl ActivityKind {
+    pub const ALL: [ActivityKind; 5] = [
+        ActivityKind::Solve,
+        ActivityKind::FirstBlood,
+        ActivityKind::NewTeam,
+        ActivityKind::Announcement,
+        ActivityKind::AdminChange,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ActivityKind::Solve => "Solves",
+            ActivityKind::FirstBlood => "First bloods",
+            ActivityKind::NewTeam => "New teams",
+            ActivityKind::Announcement => "Announcements",
+            ActivityKind::AdminChange => "Admin changes",
+        }
+    }
+
+    // Dracula colors
+    pub fn color(self) -> &'static str {
+        match self {
+            ActivityKind::Solve => "#50fa7b",
+            ActivityKind::FirstBlood => "#ff5555",
+            ActivityKind::NewTeam => "#8be9fd",
+            ActivityKind::Announcement => "#f1fa8c",
+            ActivityKind::AdminChange => "#ffb86c",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Activity {
+    pub kind: ActivityKind,
+    // Server time in ms since epoch
+    pub at: f64,
+    pub text: String,
+}
+
+///
+/// Latest competition events, newest first.
+///
+pub struct ActivityFeed {
+    entries: VecDeque<Activity>,
+    // Oldest entries are dropped beyond this
+    capacity: usize,
+}
+
+impl ActivityFeed {
+    pub fn new(capacity: usize) -> ActivityFeed {
+        ActivityFeed {
+            entries: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    ///
+    /// Add an event, kept in time order even if it arrives late.
+    ///
+    /// * `activity` - Event to add.
+    pub fn push(&mut self, activity: Activity) {
+        let index = self
+            .entries
+            .iter()
+            .position(|entry| entry.at <= activity.at)
+            .unwrap_or(self.entries.len());
+        self.entries.insert(index, activity);
+        self.entries.truncate(self.capacity);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Activity> {
+        self.entries.iter()
+    }
+}
+
+///
+/// Time since an event, e.g. `5 min ago`.
+///
+/// * `ms` - Elapsed time in ms, negative values count as now.
+pub fn format_relative(ms: f64) -> String {
+    let seconds = (ms / 1000.0).max(0.0) as i64;
+    match seconds {
+        0..=9 => "just now".to_string(),
+        10..=59 => format!("{} s ago", seconds),
+        60..=3599 => format!("{} min ago", seconds / 60),
+        3600..=86399 => format!("{} h ago", seconds / 3600),
+        _ => format!("{} d ago", seconds / 86400),
+    }
+}
@@ -0,0 +1,161 @@
+use crate::{Challenge, Id};
+use std::collections::HashMap;
+
+/// Problem found while validating the challenge prerequisites.
+#[derive(Clone, Debug, PartialEq)]
+pub enum GraphError {
+    /// A prerequisite points to a challenge that does not exist.
+    UnknownPrerequisite { challenge: Id, prerequisite: Id },
+    /// Challenges that (indirectly) require themselves, in dependency order.
+    /// The first challenge is repeated at the end.
+    Cycle(Vec<Id>),
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Visit {
+    InProgress,
+    Done,
+}
+
+///
+/// Validate the prerequisite graph of the challenges.
+///
+/// * `challenges` - All challenges of the event.
+pub fn validate(challenges: &[Challenge]) -> Vec<GraphError> {
+    let by_id: HashMap<&str, &Challenge> = challenges
+        .iter()
+        .map(|challenge| (challenge.id.as_str(), challenge))
+        .collect();
+
+    let mut errors: Vec<GraphError> = challenges
+        .iter()
+        .flat_map(|challenge| {
+            challenge
+                .prerequisites
+                .iter()
+                .filter(|prerequisite| !by_id.contains_key(prerequisite.as_str()))
+                .map(move |prerequisite| GraphError::UnknownPrerequisite {
+                    challenge: challenge.id.clone(),
+                    prerequisite: prerequisite.clone(),
+                })
+        })
+        .collect();
+
+    let mut visits = HashMap::new();
+    let mut path = Vec::new();
+    for challenge in challenges {
+        if let Some(cycle) = find_cycle(challenge, &by_id, &mut visits, &mut path) {
+            errors.push(GraphError::Cycle(cycle));
+        }
+    }
+    errors
+}
+
+//
+// Depth first search, a challenge met again while still in progress closes a cycle
+//
+fn find_cycle<'a>(
+    challenge: &'a Challenge,
+    by_id: &HashMap<&str, &'a Challenge>,
+    visits: &mut HashMap<&'a str, Visit>,
+    path: &mut Vec<&'a str>,
+) -> Option<Vec<Id>> {
+    match visits.get(challenge.id.as_str()) {
+        Some(Visit::Done) => return None,
+        Some(Visit::InProgress) => {
+            let start = path.iter().position(|id| *id == challenge.id)?;
+            let mut cycle: Vec<Id> = path[start..].iter().map(|id| id.to_string()).collect();
+            cycle.push(challenge.id.clone());
+            return Some(cycle);
+        }
+        None => {}
+    }
+
+    visits.insert(challenge.id.as_str(), Visit::InProgress);
+    path.push(challenge.id.as_str());
+    let mut cycle = None;
+    for prerequisite in &challenge.prerequisites {
+        if let Some(next) = by_id.get(prerequisite.as_str()) {
+            cycle = find_cycle(next, by_id, visits, path);
+            if cycle.is_some() {
+                break;
+            }
+        }
+    }
+    path.pop();
+    visits.insert(challenge.id.as_str(), Visit::Done);
+    cycle
+}
+
+///
+/// Prerequisites of a challenge the team has not solved yet.
+///
+/// * `challenge` - Challenge to check.
+/// * `is_solved` - Whether the team solved the challenge with the given id.
+pub fn missing_prerequisites<'a>(
+    challenge: &'a Challenge,
+    is_solved: impl Fn(&str) -> bool,
+) -> Vec<&'a Id> {
+    challenge
+        .prerequisites
+        .iter()
+        .filter(|prerequisite| !is_solved(prerequisite))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn challenge(id: &str, prerequisites: &[&str]) -> Challenge {
+        Challenge {
+            id: id.to_string(),
+            letter: id.to_uppercase(),
+            name: id.to_string(),
+            category: "misc".to_string(),
+            author: None,
+            points: 100,
+            value: 100,
+            description: String::new(),
+            attachments: vec![],
+            prerequisites: prerequisites.iter().map(|id| id.to_string()).collect(),
+            releases_at: None,
+        }
+    }
+
+    fn ids(ids: &[&str]) -> Vec<Id> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn reports_a_self_loop() {
+        let challenges = vec![challenge("a", &["a"])];
+        assert_eq!(validate(&challenges), vec![GraphError::Cycle(ids(&["a", "a"]))]);
+    }
+
+    #[test]
+    fn reports_a_cycle_in_dependency_order() {
+        let challenges = vec![challenge("a", &["b"]), challenge("b", &["c"]), challenge("c", &["a"])];
+        assert_eq!(validate(&challenges), vec![GraphError::Cycle(ids(&["a", "b", "c", "a"]))]);
+    }
+
+    #[test]
+    fn accepts_a_diamond() {
+        let challenges = vec![
+            challenge("a", &[]),
+            challenge("b", &["a"]),
+            challenge("c", &["a"]),
+            challenge("d", &["b", "c"]),
+        ];
+        assert!(validate(&challenges).is_empty());
+    }
+
+    #[test]
+    fn reports_an_unknown_prerequisite() {
+        let challenges = vec![challenge("a", &[]), challenge("b", &["a", "x"])];
+        assert_eq!(
+            validate(&challenges),
+            vec![GraphError::UnknownPrerequisite { challenge: "b".to_string(), prerequisite: "x".to_string() }]
+        );
+    }
+}
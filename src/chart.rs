@@ -0,0 +1,499 @@
+use seed::{prelude::*, *};
+
+// Dracula colors, same as the rest of the UI
+pub const PALETTE: [&str; 8] = [
+    "#50fa7b", "#9580ff", "#ff5555", "#f1fa8c", "#ffb86c", "#8be9fd", "#ff79c6", "#bd93f9",
+];
+const AXIS_COLOR: &str = "#6272a4";
+const TEXT_COLOR: &str = "#FFFFFF";
+const BACKGROUND_COLOR: &str = "#282a36";
+const EMPTY_CELL_COLOR: &str = "#44475a";
+
+const WIDTH: f64 = 800.0;
+const HEIGHT: f64 = 320.0;
+const MARGIN_LEFT: f64 = 60.0;
+const MARGIN_RIGHT: f64 = 170.0;
+const MARGIN_TOP: f64 = 30.0;
+const MARGIN_BOTTOM: f64 = 40.0;
+const TICKS: usize = 5;
+
+/// One line of a chart.
+pub struct Series {
+    pub name: String,
+    /// (x, y) points sorted by x.
+    pub points: Vec<(f64, f64)>,
+}
+
+/// Line chart with a time x-axis.
+pub struct LineChart<'a> {
+    pub title: String,
+    pub y_label: String,
+    pub series: Vec<Series>,
+    /// Label of a value on the x-axis, e.g. the time of day.
+    pub x_format: &'a dyn Fn(f64) -> String,
+}
+
+/// Filled cell of a heatmap.
+pub struct HeatmapCell {
+    /// Position on the color scale, 0 to 1.
+    pub value: f64,
+    /// Short text drawn in the cell.
+    pub label: String,
+    pub tooltip: String,
+}
+
+/// Grid of cells colored from green (low) to red (high).
+pub struct Heatmap {
+    pub title: String,
+    pub rows: Vec<String>,
+    pub columns: Vec<String>,
+    /// One `Vec` per row, `None` for empty cells.
+    pub cells: Vec<Vec<Option<HeatmapCell>>>,
+    /// Meaning of the ends of the color scale.
+    pub low_label: String,
+    pub high_label: String,
+}
+
+//
+// Linear mapping from a value range to a pixel range
+//
+struct Scale {
+    min: f64,
+    max: f64,
+    from: f64,
+    to: f64,
+}
+
+impl Scale {
+    fn map(&self, value: f64) -> f64 {
+        if (self.max - self.min).abs() < f64::EPSILON {
+            return (self.from + self.to) / 2.0;
+        }
+        self.from + (value - self.min) / (self.max - self.min) * (self.to - self.from)
+    }
+}
+
+///
+/// Render a line chart as SVG nodes.
+///
+/// Every point has a tooltip with the series name and the values.
+///
+/// * `chart` - Chart to render.
+pub fn line_chart<Ms>(chart: &LineChart) -> Node<Ms> {
+    let points = chart.series.iter().flat_map(|series| series.points.iter());
+    let (x_min, x_max) = points
+        .clone()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), (x, _)| (min.min(*x), max.max(*x)));
+    if !x_min.is_finite() {
+        return empty![];
+    }
+    let y_max = points.fold(0.0_f64, |max, (_, y)| max.max(*y));
+    let y_step = nice_step(y_max, TICKS);
+    let y_max = (y_max / y_step).ceil().max(1.0) * y_step;
+
+    let x_scale = Scale { min: x_min, max: x_max, from: MARGIN_LEFT, to: WIDTH - MARGIN_RIGHT };
+    let y_scale = Scale { min: 0.0, max: y_max, from: HEIGHT - MARGIN_BOTTOM, to: MARGIN_TOP };
+
+    svg![
+        attrs! {
+            At::ViewBox => format!("0 0 {} {}", WIDTH, HEIGHT),
+            At::Width => "100%",
+            At::FontFamily => "sans-serif",
+            At::FontSize => 12,
+        },
+        title![&chart.title],
+        text![
+            attrs! {
+                At::X => MARGIN_LEFT,
+                At::Y => MARGIN_TOP / 2.0,
+                At::Fill => TEXT_COLOR,
+                At::FontSize => 14,
+            },
+            &chart.title,
+        ],
+        view_y_axis(&y_scale, y_step, &chart.y_label),
+        view_x_axis(&x_scale, chart.x_format),
+        chart.series.iter().enumerate().map(|(index, series)| {
+            view_series(series, PALETTE[index % PALETTE.len()], &x_scale, &y_scale, chart.x_format)
+        }),
+        view_legend(&chart.series),
+    ]
+}
+
+fn view_y_axis<Ms>(scale: &Scale, step: f64, label: &str) -> Node<Ms> {
+    let ticks = (0..=((scale.max / step).round() as usize)).map(|index| index as f64 * step);
+    g![
+        ticks.map(|value| {
+            let y = scale.map(value);
+            g![
+                line_![attrs! {
+                    At::X1 => MARGIN_LEFT,
+                    At::X2 => WIDTH - MARGIN_RIGHT,
+                    At::Y1 => y,
+                    At::Y2 => y,
+                    At::Stroke => AXIS_COLOR,
+                    At::StrokeDashArray => if value == 0.0 { "none" } else { "2 4" },
+                }],
+                text![
+                    attrs! {
+                        At::X => MARGIN_LEFT - 8.0,
+                        At::Y => y,
+                        At::Fill => TEXT_COLOR,
+                        At::TextAnchor => "end",
+                        At::DominantBaseline => "middle",
+                    },
+                    format!("{}", value),
+                ],
+            ]
+        }),
+        text![
+            attrs! {
+                At::Transform => format!("translate(14 {}) rotate(-90)", (MARGIN_TOP + HEIGHT - MARGIN_BOTTOM) / 2.0),
+                At::Fill => TEXT_COLOR,
+                At::TextAnchor => "middle",
+            },
+            label,
+        ],
+    ]
+}
+
+fn view_x_axis<Ms>(scale: &Scale, format: &dyn Fn(f64) -> String) -> Node<Ms> {
+    let y = HEIGHT - MARGIN_BOTTOM;
+    let ticks = (0..TICKS).map(|index| scale.min + (scale.max - scale.min) * index as f64 / (TICKS - 1) as f64);
+    g![
+        line_![attrs! {
+            At::X1 => MARGIN_LEFT,
+            At::X2 => MARGIN_LEFT,
+            At::Y1 => MARGIN_TOP,
+            At::Y2 => y,
+            At::Stroke => AXIS_COLOR,
+        }],
+        ticks.map(|value| {
+            let x = scale.map(value);
+            g![
+                line_![attrs! {
+                    At::X1 => x,
+                    At::X2 => x,
+                    At::Y1 => y,
+                    At::Y2 => y + 5.0,
+                    At::Stroke => AXIS_COLOR,
+                }],
+                text![
+                    attrs! {
+                        At::X => x,
+                        At::Y => y + 20.0,
+                        At::Fill => TEXT_COLOR,
+                        At::TextAnchor => "middle",
+                    },
+                    format(value),
+                ],
+            ]
+        }),
+    ]
+}
+
+fn view_series<Ms>(
+    series: &Series,
+    color: &str,
+    x_scale: &Scale,
+    y_scale: &Scale,
+    x_format: &dyn Fn(f64) -> String,
+) -> Node<Ms> {
+    let points = series
+        .points
+        .iter()
+        .map(|(x, y)| format!("{:.1},{:.1}", x_scale.map(*x), y_scale.map(*y)))
+        .collect::<Vec<_>>()
+        .join(" ");
+    g![
+        polyline![attrs! {
+            At::Points => points,
+            At::Fill => "none",
+            At::Stroke => color,
+            At::StrokeWidth => 2,
+        }],
+        // Only mark points where the value changes, plus the last one
+        series.points.iter().enumerate()
+            .filter(|(index, (_, y))| {
+                *index + 1 == series.points.len()
+                    || series.points.get(index + 1).map_or(false, |(_, next)| next != y)
+            })
+            .map(|(_, (x, y))| circle![
+                attrs! {
+                    At::Cx => x_scale.map(*x),
+                    At::Cy => y_scale.map(*y),
+                    At::R => 4,
+                    At::Fill => color,
+                },
+                title![format!("{}: {} at {}", series.name, y, x_format(*x))],
+            ]),
+    ]
+}
+
+fn view_legend<Ms>(series: &[Series]) -> Node<Ms> {
+    let x = WIDTH - MARGIN_RIGHT + 20.0;
+    g![
+        series.iter().enumerate().map(|(index, series)| {
+            let y = MARGIN_TOP + index as f64 * 20.0;
+            g![
+                rect![attrs! {
+                    At::X => x,
+                    At::Y => y - 6.0,
+                    At::Width => 12,
+                    At::Height => 12,
+                    At::Fill => PALETTE[index % PALETTE.len()],
+                }],
+                text![
+                    attrs! {
+                        At::X => x + 18.0,
+                        At::Y => y,
+                        At::Fill => TEXT_COLOR,
+                        At::DominantBaseline => "middle",
+                    },
+                    &series.name,
+                ],
+            ]
+        }),
+    ]
+}
+
+///
+/// Render a small bar chart that fits in a table cell.
+///
+/// Every bar has a tooltip with its label and count.
+///
+/// * `counts` - Height of each bar.
+/// * `max_count` - Count of a full height bar, shared by the charts being compared.
+/// * `color` - Fill of the bars.
+/// * `bar_label` - Label of a bar from its index.
+pub fn histogram<Ms>(counts: &[usize], max_count: usize, color: &str, bar_label: &dyn Fn(usize) -> String) -> Node<Ms> {
+    const BAR_WIDTH: f64 = 10.0;
+    const BAR_HEIGHT: f64 = 30.0;
+    let width = BAR_WIDTH * counts.len() as f64;
+    svg![
+        attrs! {
+            At::ViewBox => format!("0 0 {} {}", width, BAR_HEIGHT + 1.0),
+            At::Width => width,
+            At::Height => BAR_HEIGHT + 1.0,
+        },
+        line_![attrs! {
+            At::X1 => 0,
+            At::X2 => width,
+            At::Y1 => BAR_HEIGHT + 0.5,
+            At::Y2 => BAR_HEIGHT + 0.5,
+            At::Stroke => AXIS_COLOR,
+        }],
+        counts.iter().enumerate().map(|(index, count)| {
+            let height = BAR_HEIGHT * *count as f64 / max_count.max(1) as f64;
+            rect![
+                attrs! {
+                    At::X => index as f64 * BAR_WIDTH + 1.0,
+                    At::Y => BAR_HEIGHT - height,
+                    At::Width => BAR_WIDTH - 2.0,
+                    At::Height => height,
+                    At::Fill => color,
+                },
+                title![format!("{}: {}", bar_label(index), count)],
+            ]
+        }),
+    ]
+}
+
+///
+/// Render one labeled histogram per row, with shared bars and scale.
+///
+/// * `title` - Title of the chart.
+/// * `caption` - What the bars stand for, shown below the rows.
+/// * `rows` - Label and bar heights of each row.
+/// * `bar_label` - Label of a bar from its index.
+pub fn histogram_rows<Ms>(
+    title: &str,
+    caption: &str,
+    rows: &[(String, Vec<usize>)],
+    bar_label: &dyn Fn(usize) -> String,
+) -> Node<Ms> {
+    const ROW_LABEL_WIDTH: f64 = 220.0;
+    const ROW_HEIGHT: f64 = 40.0;
+    const HEADER_HEIGHT: f64 = 36.0;
+    let bars = rows.iter().map(|(_, counts)| counts.len()).max().unwrap_or(0);
+    let max_count = rows.iter().flat_map(|(_, counts)| counts.iter()).copied().max().unwrap_or(0);
+    let width = ROW_LABEL_WIDTH + 10.0 * bars as f64 + 80.0;
+    let height = HEADER_HEIGHT + ROW_HEIGHT * rows.len() as f64 + 30.0;
+    svg![
+        attrs! {
+            At::ViewBox => format!("0 0 {} {}", width, height),
+            At::Width => "100%",
+            At::FontFamily => "sans-serif",
+            At::FontSize => 12,
+        },
+        title![title],
+        text![
+            attrs! { At::X => 0, At::Y => 18, At::Fill => TEXT_COLOR, At::FontSize => 14 },
+            title,
+        ],
+        rows.iter().enumerate().map(|(index, (label, counts))| {
+            let y = HEADER_HEIGHT + index as f64 * ROW_HEIGHT;
+            g![
+                text![
+                    attrs! {
+                        At::X => ROW_LABEL_WIDTH - 8.0,
+                        At::Y => y + ROW_HEIGHT / 2.0,
+                        At::Fill => TEXT_COLOR,
+                        At::TextAnchor => "end",
+                        At::DominantBaseline => "middle",
+                    },
+                    label,
+                ],
+                g![
+                    attrs! { At::Transform => format!("translate({} {})", ROW_LABEL_WIDTH, y + 4.0) },
+                    histogram(counts, max_count, PALETTE[1], bar_label),
+                ],
+            ]
+        }),
+        text![
+            attrs! {
+                At::X => ROW_LABEL_WIDTH,
+                At::Y => height - 10.0,
+                At::Fill => AXIS_COLOR,
+            },
+            caption,
+        ],
+    ]
+}
+
+///
+/// Render a heatmap as SVG nodes, sized for a projector.
+///
+/// * `heatmap` - Heatmap to render.
+pub fn heatmap<Ms>(heatmap: &Heatmap) -> Node<Ms> {
+    const CELL: f64 = 32.0;
+    const ROW_LABEL_WIDTH: f64 = 160.0;
+    const HEADER_HEIGHT: f64 = 56.0;
+    const LEGEND_HEIGHT: f64 = 40.0;
+    let width = ROW_LABEL_WIDTH + CELL * heatmap.columns.len().max(4) as f64;
+    let height = HEADER_HEIGHT + CELL * heatmap.rows.len() as f64 + LEGEND_HEIGHT;
+    let legend_y = height - LEGEND_HEIGHT + 14.0;
+    let legend_steps = 10;
+    svg![
+        attrs! {
+            At::ViewBox => format!("0 0 {} {}", width, height),
+            At::Width => "100%",
+            At::FontFamily => "sans-serif",
+            At::FontSize => 14,
+        },
+        title![&heatmap.title],
+        text![
+            attrs! { At::X => 0, At::Y => 18, At::Fill => TEXT_COLOR, At::FontSize => 16 },
+            &heatmap.title,
+        ],
+        heatmap.columns.iter().enumerate().map(|(column, label)| text![
+            attrs! {
+                At::X => ROW_LABEL_WIDTH + (column as f64 + 0.5) * CELL,
+                At::Y => HEADER_HEIGHT - 10.0,
+                At::Fill => TEXT_COLOR,
+                At::TextAnchor => "middle",
+            },
+            label,
+        ]),
+        heatmap.rows.iter().zip(&heatmap.cells).enumerate().map(|(row, (label, cells))| {
+            let y = HEADER_HEIGHT + row as f64 * CELL;
+            g![
+                text![
+                    attrs! {
+                        At::X => ROW_LABEL_WIDTH - 8.0,
+                        At::Y => y + CELL / 2.0,
+                        At::Fill => TEXT_COLOR,
+                        At::TextAnchor => "end",
+                        At::DominantBaseline => "middle",
+                    },
+                    label,
+                ],
+                cells.iter().enumerate().map(|(column, cell)| {
+                    let x = ROW_LABEL_WIDTH + column as f64 * CELL;
+                    let fill = match cell {
+                        Some(cell) => gradient(cell.value),
+                        None => EMPTY_CELL_COLOR.to_string(),
+                    };
+                    g![
+                        rect![attrs! {
+                            At::X => x + 1.0,
+                            At::Y => y + 1.0,
+                            At::Width => CELL - 2.0,
+                            At::Height => CELL - 2.0,
+                            At::Fill => fill,
+                        }],
+                        cell.as_ref().map(|cell| text![
+                            attrs! {
+                                At::X => x + CELL / 2.0,
+                                At::Y => y + CELL / 2.0,
+                                At::Fill => BACKGROUND_COLOR,
+                                At::FontSize => 11,
+                                At::TextAnchor => "middle",
+                                At::DominantBaseline => "middle",
+                            },
+                            &cell.label,
+                        ]),
+                        cell.as_ref().map(|cell| title![&cell.tooltip]),
+                    ]
+                }),
+            ]
+        }),
+        // Color scale
+        text![
+            attrs! {
+                At::X => ROW_LABEL_WIDTH - 8.0,
+                At::Y => legend_y + 6.0,
+                At::Fill => TEXT_COLOR,
+                At::TextAnchor => "end",
+                At::DominantBaseline => "middle",
+            },
+            &heatmap.low_label,
+        ],
+        (0..legend_steps).map(|step| rect![attrs! {
+            At::X => ROW_LABEL_WIDTH + step as f64 * 12.0,
+            At::Y => legend_y,
+            At::Width => 12,
+            At::Height => 12,
+            At::Fill => gradient(step as f64 / (legend_steps - 1) as f64),
+        }]),
+        text![
+            attrs! {
+                At::X => ROW_LABEL_WIDTH + legend_steps as f64 * 12.0 + 8.0,
+                At::Y => legend_y + 6.0,
+                At::Fill => TEXT_COLOR,
+                At::DominantBaseline => "middle",
+            },
+            &heatmap.high_label,
+        ],
+    ]
+}
+
+//
+// Green through yellow to red, `value` from 0 to 1
+//
+fn gradient(value: f64) -> String {
+    const STOPS: [(f64, f64, f64); 3] = [(80.0, 250.0, 123.0), (241.0, 250.0, 140.0), (255.0, 85.0, 85.0)];
+    let value = value.max(0.0).min(1.0) * (STOPS.len() - 1) as f64;
+    let index = (value as usize).min(STOPS.len() - 2);
+    let t = value - index as f64;
+    let (from, to) = (STOPS[index], STOPS[index + 1]);
+    let mix = |a: f64, b: f64| (a + (b - a) * t).round() as u8;
+    format!("#{:02x}{:02x}{:02x}", mix(from.0, to.0), mix(from.1, to.1), mix(from.2, to.2))
+}
+
+//
+// Step of 1, 2 or 5 times a power of ten giving about `ticks` ticks
+//
+fn nice_step(max: f64, ticks: usize) -> f64 {
+    if max <= 0.0 {
+        return 1.0;
+    }
+    let rough = max / ticks as f64;
+    let magnitude = 10_f64.powf(rough.log10().floor());
+    let step = [1.0, 2.0, 5.0, 10.0]
+        .iter()
+        .map(|factor| factor * magnitude)
+        .find(|step| *step >= rough)
+        .unwrap_or(10.0 * magnitude);
+    step.max(1.0)
+}
@@ -0,0 +1,84 @@
+use crate::EventSchedule;
+
+///
+/// Phase of the event, derived from the schedule and the server clock.
+///
+/// Scheduled -> Running -> (Frozen ->) Ended -> Archived
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EventPhase {
+    /// Before the start, challenges are hidden.
+    Scheduled,
+    Running,
+    /// Submissions are still accepted but the scoreboard stops updating.
+    /// The server must stop broadcasting solves and score changes and answer
+    /// queries with the standings at the freeze, the client shows a snapshot.
+    Frozen,
+    /// Submissions are closed.
+    Ended,
+    /// Results are final and kept read only.
+    Archived,
+}
+
+impl EventPhase {
+    ///
+    /// Phase of the event at the given time.
+    ///
+    /// * `schedule` - Event configuration from the server.
+    /// * `now` - Server time in ms since epoch.
+    pub fn at(schedule: &EventSchedule, now: f64) -> EventPhase {
+        if schedule.archived {
+            EventPhase::Archived
+        } else if now < schedule.starts_at {
+            EventPhase::Scheduled
+        } else if now >= schedule.ends_at {
+            EventPhase::Ended
+        } else if schedule.freezes_at.map_or(false, |freezes_at| now >= freezes_at) {
+            EventPhase::Frozen
+        } else {
+            EventPhase::Running
+        }
+    }
+
+    ///
+    /// Server time of the next phase change, if one is scheduled.
+    ///
+    /// Archiving is done by an admin and has no time.
+    ///
+    /// * `schedule` - Event configuration from the server.
+    pub fn next_change(self, schedule: &EventSchedule) -> Option<f64> {
+        match self {
+            EventPhase::Scheduled => Some(schedule.starts_at),
+            EventPhase::Running => Some(
+                schedule
+                    .freezes_at
+                    .filter(|freezes_at| *freezes_at < schedule.ends_at)
+                    .unwrap_or(schedule.ends_at),
+            ),
+            EventPhase::Frozen => Some(schedule.ends_at),
+            EventPhase::Ended | EventPhase::Archived => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            EventPhase::Scheduled => "Scheduled",
+            EventPhase::Running => "Running",
+            EventPhase::Frozen => "Scoreboard frozen",
+            EventPhase::Ended => "Ended",
+            EventPhase::Archived => "Archived",
+        }
+    }
+
+    pub fn challenges_visible(self) -> bool {
+        self != EventPhase::Scheduled
+    }
+
+    pub fn submissions_open(self) -> bool {
+        self == EventPhase::Running || self == EventPhase::Frozen
+    }
+
+    pub fn scoreboard_live(self) -> bool {
+        self == EventPhase::Running
+    }
+}
@@ -0,0 +1,80 @@
+use seed::prelude::*;
+use std::ops::Range;
+use std::str::FromStr;
+
+const DEFAULT_PAGE_SIZE: usize = 10;
+const DEFAULT_INTERVAL_SECS: u32 = 15;
+const DEFAULT_TOP_TEAMS: usize = 5;
+// Larger values would overflow the page arithmetic on wasm32
+const MAX_PAGE_SIZE: usize = 1000;
+const MAX_TOP_TEAMS: usize = 100;
+
+///
+/// Unattended scoreboard for a wall display, paging through the teams.
+///
+/// Enabled with `?kiosk`, tuned with `page_size`, `interval` (seconds)
+/// and `top` (teams in the graph), e.g. `?kiosk&page_size=12&interval=20`.
+pub struct Kiosk {
+    pub page_size: usize,
+    pub interval_ms: u32,
+    pub top_teams: usize,
+    page: usize,
+}
+
+impl Kiosk {
+    ///
+    /// Kiosk settings from the query string, `None` without the `kiosk` flag.
+    ///
+    /// * `url` - Url the app was loaded with.
+    pub fn from_url(url: &Url) -> Option<Kiosk> {
+        let search = url.search();
+        let enabled = search
+            .get("kiosk")
+            .map_or(false, |values| values.iter().all(|value| value != "0" && value != "false"));
+        if !enabled {
+            return None;
+        }
+        Some(Kiosk {
+            page_size: param(search, "page_size").filter(|size| *size > 0 && *size <= MAX_PAGE_SIZE).unwrap_or(DEFAULT_PAGE_SIZE),
+            interval_ms: param(search, "interval").filter(|secs| *secs > 0 && *secs <= 3600).unwrap_or(DEFAULT_INTERVAL_SECS) * 1000,
+            top_teams: param(search, "top").filter(|top| *top > 0 && *top <= MAX_TOP_TEAMS).unwrap_or(DEFAULT_TOP_TEAMS),
+            page: 0,
+        })
+    }
+
+    pub fn page_count(&self, team_count: usize) -> usize {
+        (team_count.saturating_add(self.page_size - 1) / self.page_size).max(1)
+    }
+
+    ///
+    /// Move to the next page, back to the first one after the last.
+    ///
+    /// * `team_count` - Number of teams on the scoreboard.
+    pub fn next_page(&mut self, team_count: usize) {
+        self.page = (self.page + 1) % self.page_count(team_count);
+    }
+
+    ///
+    /// Current page, 0 based, stays valid when teams are removed.
+    ///
+    /// * `team_count` - Number of teams on the scoreboard.
+    pub fn page(&self, team_count: usize) -> usize {
+        self.page.min(self.page_count(team_count) - 1)
+    }
+
+    ///
+    /// Ranks shown on the current page, 0 based.
+    ///
+    /// * `team_count` - Number of teams on the scoreboard.
+    pub fn ranks(&self, team_count: usize) -> Range<usize> {
+        let start = self.page(team_count) * self.page_size;
+        start..start.saturating_add(self.page_size).min(team_count)
+    }
+}
+
+//
+// First value of a query parameter, `None` if missing or invalid
+//
+fn param<T: FromStr>(search: &UrlSearch, key: &str) -> Option<T> {
+    search.get(key).and_then(|values| values.first()).and_then(|value| value.parse().ok())
+}
@@ -1,10 +1,30 @@
 use graphql_client::{GraphQLQuery, Response as GQLResponse};
+use std::collections::HashMap;
+mod chart;
+mod activity;
+mod challenge_graph;
+mod event_phase;
+mod kiosk;
+mod markdown;
+mod page;
+mod score_history;
+mod session;
 mod shared;
+mod solve_stats;
+mod svg_export;
+mod text_chart;
+mod time_sync;
+mod time_zone;
 use seed::{prelude::*, *};
 use serde::{Deserialize, Serialize};
 // Allows sort_by
 use itertools::Itertools;
-use rasciigraph::{plot, Config};
+use activity::{Activity, ActivityFeed, ActivityKind};
+use event_phase::EventPhase;
+use kiosk::Kiosk;
+use time_zone::TimeZoneChoice;
+use score_history::ScoreHistory;
+use session::Session;
 
 // Global types and Constant values
 type Id = String;
@@ -15,6 +35,31 @@ type Points = String;
 // TODO: Change these urls for production
 const API_URL: &str = "http://c2.local:8000";
 const WS_URL: &str = "ws://c2.local:8000";
+const SOLVES_SUBSCRIPTION_ID: &str = "solves";
+const ANNOUNCEMENTS_SUBSCRIPTION_ID: &str = "announcements";
+const TICK_MS: u32 = 1000;
+// Scores are sampled on every change and at least this often
+const GRAPH_SAMPLE_MS: f64 = 30.0 * 1000.0;
+// Number of samples kept for the graph
+const GRAPH_CAPACITY: usize = 500;
+// Number of entries kept in the activity feed
+const ACTIVITY_CAPACITY: usize = 100;
+// The progress bar turns to a warning for the last 15 minutes
+const FINAL_STRETCH_MS: f64 = 15.0 * 60.0 * 1000.0;
+// Re-estimate the server clock offset every 5 minutes
+const CLOCK_SYNC_INTERVAL_MS: f64 = 5.0 * 60.0 * 1000.0;
+// Ask again for a released challenge every 2 seconds, a few times in case
+// the server clock is behind the estimate
+const RELEASE_RETRY_MS: f64 = 2.0 * 1000.0;
+const RELEASE_RETRIES: usize = 3;
+
+// Url path parts of the pages
+const CHALLENGES: &str = "challenges";
+const TEAMS: &str = "teams";
+const PROFILE: &str = "profile";
+const LOGIN: &str = "login";
+const SIGN_UP: &str = "signup";
+const ADMIN: &str = "admin";
 
 // ------ ------
 //    GraphQL
@@ -38,14 +83,61 @@ generate_query!(QBooks);
 generate_query!(MCreateBook);
 generate_query!(MUpdateBook);
 generate_query!(MDeleteBook);
+generate_query!(QChallenges);
+generate_query!(MSubmitFlag);
+generate_query!(QHints);
+generate_query!(MUnlockHint);
+generate_query!(QAttachmentUrl);
+generate_query!(QSolves);
+generate_query!(QAttempts);
+generate_query!(QTeam);
+generate_query!(QEvent);
+generate_query!(MSignUp);
+generate_query!(MLogIn);
+
+//
+// Fetch the event schedule, the request times are used to estimate
+// the server clock offset
+//
+fn fetch_event(orders: &mut impl Orders<Msg>) {
+    orders.perform_cmd(async {
+        let sent_at = js_sys::Date::now();
+        let response = send_graphql_request(&QEvent::build_query(q_event::Variables)).await;
+        Msg::EventFetched(sent_at, js_sys::Date::now(), response)
+    });
+}
+
+fn fetch_books(orders: &mut impl Orders<Msg>) {
+    orders.perform_cmd(async {
+        Msg::BooksFetched(send_graphql_request(&QBooks::build_query(q_books::Variables)).await)
+    });
+}
+
+fn fetch_solves(orders: &mut impl Orders<Msg>) {
+    orders.perform_cmd(async {
+        Msg::SolvesFetched(send_graphql_request(&QSolves::build_query(q_solves::Variables)).await)
+    });
+}
+
+fn fetch_challenges(orders: &mut impl Orders<Msg>) {
+    orders.perform_cmd(async {
+        Msg::ChallengesFetched(
+            send_graphql_request(&QChallenges::build_query(q_challenges::Variables)).await
+        )
+    });
+}
 
 async fn send_graphql_request<V, T>(variables: &V) -> fetch::Result<T>
 where
     V: Serialize,
     T: for<'de> Deserialize<'de> + 'static,
 {
-    Request::new(API_URL)
-        .method(Method::Post)
+    let mut request = Request::new(API_URL).method(Method::Post);
+    // Signed in players act with their own permissions
+    if let Some(session) = Session::load() {
+        request = request.header(Header::bearer(session.token));
+    }
+    request
         .json(variables)?
         .fetch()
         .await?
@@ -58,40 +150,72 @@ where
 //     Init
 // ------ ------
 
-fn init(_: Url, orders: &mut impl Orders<Msg>) -> Model {
+fn init(url: Url, orders: &mut impl Orders<Msg>) -> Model {
     //
     // GraphQL Query fetch data
     //
-    orders.perform_cmd(async {
-        Msg::BooksFetched(send_graphql_request(&QBooks::build_query(q_books::Variables)).await)
-    });
+    fetch_books(orders);
+    fetch_challenges(orders);
+    fetch_event(orders);
+    fetch_solves(orders);
+
+    //
+    // Pause the ticker while the tab is hidden
+    //
+    orders.stream(streams::document_event(Ev::VisibilityChange, |_| Msg::VisibilityChanged));
+
+    orders.subscribe(Msg::UrlChanged);
+
+    //
+    // Wall display, `?kiosk` in the url
+    //
+    let kiosk = Kiosk::from_url(&url);
+    if let Some(kiosk) = &kiosk {
+        orders.stream(streams::interval(kiosk.interval_ms, || Msg::KioskNextPage));
+    }
 
     //
     // Init Model default values
     //
     Model {
+        base_url: url.to_base_url(),
+        page: Page::init(url, orders),
         // books: Option::Some(Vec::new()),
         messages: Vec::new(),
-        input_text_name: String::new(),
-        input_text_author: String::new(),
-        input_text_points: std::default::Default::default(),
         web_socket: create_websocket(orders),
         web_socket_reconnector: None,
-        selected_name: std::default::Default::default(),
-        selected_author: std::default::Default::default(),
-        selected_points: std::default::Default::default(),
         selected_id: std::default::Default::default(),
-        seconds: 0,
-        //
-        // Generate 10 vec values = 0.0
-        //
-        graph: vec![0.0; 100],
-        problems: vec![],
-        timer_handle: Some(orders.stream_with_handle(streams::interval(1000, || Msg::OnTick))),
+        event: None,
+        phase: None,
+        time_sync: time_sync::TimeSync::new(),
+        last_clock_sync: js_sys::Date::now(),
+        now: js_sys::Date::now(),
+        time_zone: TimeZoneChoice::load(),
+        score_history: ScoreHistory::new(GRAPH_CAPACITY),
+        score_history_incomplete: false,
+        challenges: vec![],
+        releases_fetched_at: 0.0,
+        release_refetches: HashMap::new(),
+        solves: vec![],
+        submissions: vec![],
+        player_team_id: None,
+        hints: vec![],
+        ledger: vec![],
+        activity: ActivityFeed::new(ACTIVITY_CAPACITY),
+        kiosk,
+        timer_handle: Some(start_ticker(orders)),
+        ticker_paused: false,
+        frozen_scoreboard: None,
+        session: Session::load(),
+        user_menu_open: false,
     }
 }
 
 
+fn start_ticker(orders: &mut impl Orders<Msg>) -> StreamHandle {
+    orders.stream_with_handle(streams::interval(TICK_MS, || Msg::OnTick))
+}
+
 //
 // websocket client connect to server
 //
@@ -110,30 +234,134 @@ fn create_websocket(orders: &impl Orders<Msg>) -> WebSocket {
 // ------ ------
 
 struct Model {
+    // Url the pages are relative to
+    base_url: Url,
+    page: Page,
     messages: Vec<Message>,
-    input_text_name: String,
-    input_text_author: String,
-    input_text_points: String,
-    selected_name: Option<Name>,
-    selected_author: Option<Author>,
-    selected_points: Option<Points>,
     selected_id: Option<Id>,
     web_socket: WebSocket,
     web_socket_reconnector: Option<StreamHandle>,
     // books: Option<Vec<q_books::QBooksBooks>>,
-    seconds: i64,
-    graph: Vec<f64>,
-    problems: Vec<Problem>,
+    event: Option<EventSchedule>,
+    // None until the schedule is known
+    phase: Option<EventPhase>,
+    time_sync: time_sync::TimeSync,
+    // Local time of the last clock sync request
+    last_clock_sync: f64,
+    // Server time of the last tick in ms since epoch
+    now: f64,
+    // Zone absolute times are shown in
+    time_zone: TimeZoneChoice,
+    score_history: ScoreHistory,
+    // Solves of unknown challenges were left out of the rebuilt score history
+    score_history_incomplete: bool,
+    challenges: Vec<Challenge>,
+    // Local time of the last refetch for released challenges
+    releases_fetched_at: f64,
+    // Refetches since the release per challenge seen before its release
+    release_refetches: HashMap<Id, usize>,
+    solves: Vec<Solve>,
+    submissions: Vec<Submission>,
+    // Team the flags are submitted for
+    player_team_id: Option<Id>,
+    hints: Vec<Hint>,
+    ledger: Vec<LedgerEntry>,
+    activity: ActivityFeed,
+    // None unless the scoreboard runs on a wall display
+    kiosk: Option<Kiosk>,
+    // None while paused or while the tab is hidden
     timer_handle: Option<StreamHandle>,
+    // Paused from the admin controls
+    ticker_paused: bool,
+    // Shown instead of the live standings while the scoreboard is frozen
+    frozen_scoreboard: Option<ScoreboardSnapshot>,
+    // None while signed out
+    session: Option<Session>,
+    // Dropdown under the name of the signed in player
+    user_menu_open: bool,
+}
+
+//
+// Page shown for the current url, pages with local state own a sub-model
+//
+enum Page {
+    Scoreboard(page::scoreboard::Model),
+    Challenges(page::challenges::Model),
+    Challenge(page::challenge::Model),
+    Team(page::team::Model),
+    Profile,
+    Login(page::login::Model),
+    Admin(page::admin::Model),
+    NotFound,
+}
+
+impl Page {
+    fn init(mut url: Url, orders: &mut impl Orders<Msg>) -> Page {
+        match url.next_path_part() {
+            None => Page::Scoreboard(page::scoreboard::init()),
+            Some(CHALLENGES) => match url.next_path_part() {
+                None => Page::Challenges(page::challenges::init()),
+                Some(challenge_id) => Page::Challenge(page::challenge::init(challenge_id.to_string())),
+            },
+            Some(TEAMS) => match url.next_path_part() {
+                Some(team_id) => Page::Team(page::team::init(team_id.to_string(), &mut orders.proxy(Msg::Team))),
+                None => Page::NotFound,
+            },
+            Some(PROFILE) => Page::Profile,
+            Some(LOGIN) => Page::Login(page::login::init(false)),
+            Some(SIGN_UP) => Page::Login(page::login::init(true)),
+            Some(ADMIN) => Page::Admin(page::admin::init()),
+            Some(_) => Page::NotFound,
+        }
+    }
+}
+
+struct_urls!();
+impl<'a> Urls<'a> {
+    pub fn scoreboard(self) -> Url {
+        self.base_url()
+    }
+    pub fn challenges(self) -> Url {
+        self.base_url().add_path_part(CHALLENGES)
+    }
+    pub fn challenge(self, challenge_id: &str) -> Url {
+        self.challenges().add_path_part(challenge_id)
+    }
+    pub fn team(self, team_id: &str) -> Url {
+        self.base_url().add_path_part(TEAMS).add_path_part(team_id)
+    }
+    pub fn profile(self) -> Url {
+        self.base_url().add_path_part(PROFILE)
+    }
+    pub fn login(self) -> Url {
+        self.base_url().add_path_part(LOGIN)
+    }
+    pub fn sign_up(self) -> Url {
+        self.base_url().add_path_part(SIGN_UP)
+    }
+    pub fn admin(self) -> Url {
+        self.base_url().add_path_part(ADMIN)
+    }
+}
+
+pub struct EventSchedule {
+    name: String,
+    // Server time in ms since epoch
+    starts_at: f64,
+    ends_at: f64,
+    freezes_at: Option<f64>,
+    archived: bool,
+    // IANA name
+    time_zone: String,
 }
 
 // Parse GraphQL Subscription Message
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Message {
     id: String,
     name: String,
     author: String,
-    points: u8,
+    points: i64,
     problems: Vec<Problem>,
 }
 
@@ -142,6 +370,116 @@ pub struct Problem {
     letter: String,
 }
 
+#[derive(Clone, Debug)]
+pub struct Challenge {
+    id: Id,
+    letter: String,
+    name: String,
+    category: String,
+    author: Option<String>,
+    // Initial value
+    points: i64,
+    // Current value, decreases with every solve
+    value: i64,
+    // Markdown
+    description: String,
+    attachments: Vec<Attachment>,
+    prerequisites: Vec<Id>,
+    // Server time in ms since epoch, None if released with the event
+    releases_at: Option<f64>,
+}
+
+impl Challenge {
+    fn is_released(&self, now: f64) -> bool {
+        self.releases_at.map_or(true, |releases_at| releases_at <= now)
+    }
+}
+
+// Parse GraphQL Subscription Solve
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Solve {
+    team_id: Id,
+    challenge_id: Id,
+    // Server time in ms since epoch
+    solved_at: f64,
+}
+
+// Public standings as they were when the scoreboard froze
+#[derive(Clone, Debug)]
+pub struct ScoreboardSnapshot {
+    messages: Vec<Message>,
+    solves: Vec<Solve>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Attachment {
+    id: Id,
+    name: String,
+    size: i64,
+    sha256: String,
+}
+
+// Result of a flag submission as shown to the player
+#[derive(Clone, Debug, PartialEq)]
+pub enum SubmissionOutcome {
+    Correct,
+    Incorrect,
+    AlreadySolved,
+    // Seconds until the next attempt is accepted, if the server told us
+    RateLimited(Option<i64>),
+    Failed(String),
+}
+
+#[derive(Clone, Debug)]
+pub struct Submission {
+    challenge_id: Id,
+    flag: String,
+    // Local time of the submission in ms since epoch
+    submitted_at: f64,
+    // Current value of the challenge when submitted, what a correct flag earns
+    value: i64,
+    // None while the request is in flight
+    outcome: Option<SubmissionOutcome>,
+}
+
+// Charts that can be exported as SVG files
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ChartKind {
+    ScoreOverTime,
+    SolveStats,
+    Heatmap,
+}
+
+impl ChartKind {
+    fn file_name(self) -> &'static str {
+        match self {
+            ChartKind::ScoreOverTime => "score-over-time.svg",
+            ChartKind::SolveStats => "solve-statistics.svg",
+            ChartKind::Heatmap => "heatmap.svg",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Hint {
+    id: Id,
+    challenge_id: Id,
+    cost: i64,
+    // Markdown, only known once unlocked
+    content: Option<String>,
+}
+
+// Score change of a team, solves add and hint unlocks subtract points
+#[derive(Clone, Debug)]
+pub struct LedgerEntry {
+    team_id: Id,
+    description: String,
+    points: i64,
+    // Local time of the change in ms since epoch
+    at: f64,
+}
+
 // Message from the server to the client.
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ServerMessage {
@@ -179,17 +517,41 @@ enum Msg {
     BookDeletedClick(Id),
     BookUpdated(fetch::Result<GQLResponse<q_books::ResponseData>>),
     BookUpdatedClick(Id, Name, Author, Points),
-    BookCreated(fetch::Result<GQLResponse<q_books::ResponseData>>),
-    BookCreatedClick(Name, Author, Points),
     WebSocketOpened,
     MessageReceived(WebSocketMessage),
     WebSocketClosed(CloseEvent),
     WebSocketFailed,
     ReconnectWebSocket(usize),
-    InputTextNameChanged(String),
-    InputTextAuthorChanged(String),
-    InputTextPointsChanged(String),
+    ChallengesFetched(fetch::Result<GQLResponse<q_challenges::ResponseData>>),
+    SolvesFetched(fetch::Result<GQLResponse<q_solves::ResponseData>>),
+    // Local time sent, local time received, response
+    EventFetched(f64, f64, fetch::Result<GQLResponse<q_event::ResponseData>>),
+    PlayerTeamChanged(Id),
+    FlagSubmittedClick(Id, String),
+    FlagSubmitted(usize, fetch::Result<GQLResponse<m_submit_flag::ResponseData>>),
+    HintsFetched(fetch::Result<GQLResponse<q_hints::ResponseData>>),
+    HintUnlockConfirmed(Id),
+    HintUnlocked(fetch::Result<GQLResponse<m_unlock_hint::ResponseData>>),
+    AttachmentDownloadClick(Id),
+    AttachmentUrlFetched(fetch::Result<GQLResponse<q_attachment_url::ResponseData>>),
     OnTick,
+    TickerPauseClick,
+    TickerResumeClick,
+    TickerResetClick,
+    VisibilityChanged,
+    TimeZoneChanged(String),
+    ChartExportClick(ChartKind),
+    KioskNextPage,
+    UrlChanged(subs::UrlChanged),
+    Admin(page::admin::Msg),
+    Challenge(page::challenge::Msg),
+    Challenges(page::challenges::Msg),
+    Login(page::login::Msg),
+    Profile(page::profile::Msg),
+    Scoreboard(page::scoreboard::Msg),
+    Team(page::team::Msg),
+    UserMenuToggled,
+    SignOutClick,
 }
 
 fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
@@ -198,68 +560,203 @@ fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
         // Interval
         //
         Msg::OnTick => {
-            if model.seconds != 600000 {
-                model.seconds += 1000;
-                if model.graph.len() < 100 {
-                    model.graph.push(1.0)
-                } else {
-                    // Remove the first duplicate value
-                    model.graph.remove(0);
-                    model.graph.push(1.0);
+            let local_now = js_sys::Date::now();
+            model.now = model.time_sync.server_time(local_now);
+            update_phase(model, orders);
+            //
+            // Periodic sample, the first tick after a pause or a hidden tab catches up
+            //
+            let sample_due = model.score_history.last_sample_at()
+                .map_or(true, |last_sample_at| model.now - last_sample_at >= GRAPH_SAMPLE_MS);
+            if sample_due {
+                sample_scores(model);
+            }
+            //
+            // Reveal challenges whose release countdown hit zero
+            //
+            let now = model.now;
+            let released: Vec<Id> = model.challenges.iter()
+                .filter(|challenge| challenge.is_released(now))
+                .filter(|challenge| model.release_refetches.get(&challenge.id).map_or(false, |count| *count < RELEASE_RETRIES))
+                .map(|challenge| challenge.id.clone())
+                .collect();
+            if !released.is_empty() && local_now - model.releases_fetched_at >= RELEASE_RETRY_MS {
+                for challenge_id in released {
+                    *model.release_refetches.entry(challenge_id).or_insert(0) += 1;
                 }
+                model.releases_fetched_at = local_now;
+                fetch_challenges(orders);
+            }
+            if local_now - model.last_clock_sync >= CLOCK_SYNC_INTERVAL_MS {
+                model.last_clock_sync = local_now;
+                fetch_event(orders);
+            }
+        }
+        Msg::TickerPauseClick => {
+            model.ticker_paused = true;
+            // Dropping the handle stops the stream
+            model.timer_handle = None;
+        }
+        Msg::TickerResumeClick => {
+            model.ticker_paused = false;
+            if model.timer_handle.is_none() && !document().hidden() {
+                model.timer_handle = Some(start_ticker(orders));
+                orders.send_msg(Msg::OnTick);
+            }
+        }
+        Msg::TickerResetClick => {
+            model.score_history.clear();
+            sample_scores(model);
+            if model.timer_handle.is_some() {
+                model.timer_handle = Some(start_ticker(orders));
+            }
+        }
+        Msg::VisibilityChanged => {
+            if document().hidden() {
+                model.timer_handle = None;
+            } else if !model.ticker_paused && model.timer_handle.is_none() {
+                model.timer_handle = Some(start_ticker(orders));
+                // Timers are throttled in background tabs, re-check the clock
+                model.last_clock_sync = js_sys::Date::now();
+                fetch_event(orders);
+                orders.send_msg(Msg::OnTick);
             }
         }
         //
-        // GraphQL functions
+        // Pages
         //
-        Msg::BooksFetched(Ok(GQLResponse {
-            data: Some(data), ..
-        })) => {
-            let vec_books_length = data.books.len();
-            for book_index in 0..vec_books_length {
-                model.messages.push(
-                    Message {
-                        id: data.books[book_index].id.to_string(),
-                        name: data.books[book_index].name.to_string(),
-                        author: data.books[book_index].author.to_string(),
-                        points: data.books[book_index].points.to_string().parse().unwrap(),
-                        problems: vec![
-                            Problem{ letter:"A".to_string() },
-                            Problem{ letter:"B".to_string() },
-                        ],
-                    }
-                );
-                // if model.problems.iter().any(|i| i.letter != "A") {
-                model.problems.push(
-                    Problem{ letter:"A".to_string() },
-                );
-                // }
+        Msg::UrlChanged(subs::UrlChanged(url)) => {
+            model.page = Page::init(url, orders);
+            model.user_menu_open = false;
+        }
+        Msg::Admin(msg) => {
+            if let Page::Admin(page_model) = &mut model.page {
+                page::admin::update(msg, page_model, &mut orders.proxy(Msg::Admin));
             }
         }
-        Msg::BookCreated(Ok(GQLResponse {
-            data: Some(_), ..
+        Msg::Team(msg) => {
+            if let Page::Team(page_model) = &mut model.page {
+                page::team::update(msg, page_model);
+            }
+        }
+        Msg::Scoreboard(msg) => {
+            if let Page::Scoreboard(page_model) = &mut model.page {
+                if let Some(action) = page::scoreboard::update(msg, page_model, &mut orders.proxy(Msg::Scoreboard)) {
+                    perform(action, orders);
+                }
+            }
+        }
+        Msg::Challenges(msg) => {
+            if let Page::Challenges(page_model) = &mut model.page {
+                if let Some(action) = page::challenges::update(msg, page_model) {
+                    perform(action, orders);
+                }
+            }
+        }
+        Msg::Challenge(msg) => {
+            if let Page::Challenge(page_model) = &mut model.page {
+                if let Some(action) = page::challenge::update(msg, page_model) {
+                    perform(action, orders);
+                }
+            }
+        }
+        Msg::Profile(msg) => {
+            if let Page::Profile = model.page {
+                if let Some(action) = page::profile::update(msg) {
+                    perform(action, orders);
+                }
+            }
+        }
+        Msg::Login(msg) => {
+            if let Page::Login(page_model) = &mut model.page {
+                if let Some(session) = page::login::update(msg, page_model, &mut orders.proxy(Msg::Login)) {
+                    session.store();
+                    model.session = Some(session);
+                    forget_player(model);
+                    orders.notify(subs::UrlRequested::new(Urls::new(&model.base_url).scoreboard()));
+                }
+            }
+        }
+        Msg::UserMenuToggled => {
+            model.user_menu_open = !model.user_menu_open;
+        }
+        Msg::SignOutClick => {
+            Session::clear();
+            model.session = None;
+            model.user_menu_open = false;
+            forget_player(model);
+        }
+        Msg::KioskNextPage => {
+            let team_count = standings_teams(model).len();
+            if let Some(kiosk) = &mut model.kiosk {
+                kiosk.next_page(team_count);
+            }
+        }
+        Msg::ChartExportClick(kind) => {
+            // Exported as shown on the page, e.g. the plotted teams
+            let chart: Node<Msg> = match (kind, &model.page) {
+                (ChartKind::ScoreOverTime, Page::Scoreboard(page_model)) => page::scoreboard::score_graph_chart(page_model, model),
+                (ChartKind::Heatmap, Page::Scoreboard(page_model)) if page::scoreboard::heatmap_plottable(page_model, model) => {
+                    page::scoreboard::heatmap_chart(page_model, model)
+                }
+                (ChartKind::SolveStats, _) if page::challenges::solve_stats_plottable(model) => {
+                    page::challenges::solve_stats_chart(model)
+                }
+                _ => return,
+            };
+            if let Node::Empty = chart {
+                return;
+            }
+            svg_export::download(kind.file_name(), &svg_export::standalone(&chart, &export_metadata(model)));
+        }
+        Msg::TimeZoneChanged(key) => {
+            if let Some(time_zone) = TimeZoneChoice::from_key(&key) {
+                model.time_zone = time_zone;
+                time_zone.store();
+            }
+        }
+        Msg::EventFetched(sent_at, received_at, Ok(GQLResponse {
+            data: Some(data), ..
         })) => {
-            log!("Created Book");
+            let event = data.event;
+            model.time_sync.add_sample(sent_at, received_at, event.server_time);
+            model.now = model.time_sync.server_time(js_sys::Date::now());
+            let first_schedule = model.event.is_none();
+            model.event = Some(EventSchedule {
+                name: event.name,
+                starts_at: event.starts_at,
+                ends_at: event.ends_at,
+                freezes_at: event.freezes_at,
+                archived: event.archived,
+                time_zone: event.time_zone,
+            });
+            update_phase(model, orders);
+            // The score graph starts with the event
+            if first_schedule {
+                seed_score_history(model);
+            }
         }
+        Msg::EventFetched(_, _, error) => log!(error),
         //
-        // Trigger query on click
+        // GraphQL functions
         //
-        Msg::BookCreatedClick(name, author, points) => {
-            model.selected_name = Some(name.clone());
-            model.selected_author = Some(author.clone());
-            model.selected_points = Some(points.clone());
-            orders.perform_cmd(async {
-                Msg::BookCreated(
-                    send_graphql_request(&MCreateBook::build_query(m_create_book::Variables {
-                        name,
-                        author,
-                        points,
-                    }))
-                    .await,
-                )
-            });
+        Msg::BooksFetched(Ok(GQLResponse {
+            data: Some(data), ..
+        })) => {
+            // Replaces the teams, books are refetched when the freeze ends
+            model.messages = data.books.iter().map(|book| Message {
+                id: book.id.to_string(),
+                name: book.name.to_string(),
+                author: book.author.to_string(),
+                points: book.points.to_string().parse().unwrap(),
+                problems: vec![
+                    Problem{ letter:"A".to_string() },
+                    Problem{ letter:"B".to_string() },
+                ],
+            }).collect();
+            snapshot_scoreboard(model);
+            seed_score_history(model);
         }
-        Msg::BookCreated(error) => log!(error),
         Msg::BookUpdated(Ok(GQLResponse {
             data: Some(_), ..
         })) => {
@@ -350,6 +847,40 @@ fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
                         },
                     })
                     .unwrap();
+                model.web_socket
+                    .send_json(&shared::ClientMessageGQLPay {
+                        id: SOLVES_SUBSCRIPTION_ID.to_string(),
+                        r#type: "start".to_string(),
+                        payload: {
+                            shared::Payload {
+                                query: "subscription {
+                                    solves {
+                                        teamId,
+                                        challengeId,
+                                        solvedAt,
+                                    }
+                                }".to_string(),
+                            }
+                        },
+                    })
+                    .unwrap();
+                model.web_socket
+                    .send_json(&shared::ClientMessageGQLPay {
+                        id: ANNOUNCEMENTS_SUBSCRIPTION_ID.to_string(),
+                        r#type: "start".to_string(),
+                        payload: {
+                            shared::Payload {
+                                query: "subscription {
+                                    announcements {
+                                        id,
+                                        message,
+                                        postedAt,
+                                    }
+                                }".to_string(),
+                            }
+                        },
+                    })
+                    .unwrap();
             }
             log!("WebSocket connection is open now");
         }
@@ -359,6 +890,41 @@ fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
             let book = &json_message["payload"]["data"]["books"];
             if json_message["type"] == "connection_ack" {
                 log!("CONNECTED");
+            } else if json_message["type"] == "data" && json_message["id"] == SOLVES_SUBSCRIPTION_ID {
+                match serde_json::from_value::<Solve>(json_message["payload"]["data"]["solves"].clone()) {
+                    Ok(solve) => {
+                        let first_blood = !model.solves.iter().any(|s| s.challenge_id == solve.challenge_id);
+                        let activity = solve_activity(model, &solve, first_blood);
+                        let team_id = solve.team_id.clone();
+                        let challenge_id = solve.challenge_id.clone();
+                        // The feed would reveal solves made after the freeze
+                        if record_solve(model, solve) && model.frozen_scoreboard.is_none() {
+                            model.activity.push(activity);
+                        }
+                        match &mut model.page {
+                            Page::Team(page_model) => {
+                                page::team::solve_received(page_model, &team_id, &mut orders.proxy(Msg::Team));
+                            }
+                            Page::Challenge(page_model) => {
+                                if page::challenge::solve_received(page_model, &challenge_id) {
+                                    fetch_challenges(orders);
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                    Err(error) => log!("Invalid solve", error.to_string()),
+                }
+            } else if json_message["type"] == "data" && json_message["id"] == ANNOUNCEMENTS_SUBSCRIPTION_ID {
+                let announcement = &json_message["payload"]["data"]["announcements"];
+                match (announcement["message"].as_str(), announcement["postedAt"].as_f64()) {
+                    (Some(message), Some(posted_at)) => model.activity.push(Activity {
+                        kind: ActivityKind::Announcement,
+                        at: posted_at,
+                        text: message.to_string(),
+                    }),
+                    _ => log!("Invalid announcement", announcement),
+                }
             } else if json_message["type"] == "data" {
                 log!("MESSAGE",json_message);
                 let mutation_type = book["mutationType"].to_string().replace("\"", "");
@@ -370,29 +936,11 @@ fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
                         Problem{ letter:"A".to_string() },
                         Problem{ letter:"B".to_string() },
                     ];
-                match mutation_type.as_str() {
-                    "CREATED" => {
-                        model.messages
-                            .push(Message { id, name, author, points, problems });
-                    }
-                    "UPDATED" => {
-                        if let Some(index) = model.messages.iter().position(|message| message.id.to_string() == id) {
-                            model.messages[index] = Message {
-                                id: id.clone(),
-                                name: name.clone(),
-                                author: author.clone(),
-                                points: points.clone(),
-                                problems: problems.clone(),
-                            }
-                        }
-                    }
-                    "DELETED" => {
-                        if let Some(index) = model.messages.iter().position(|message| message.id.to_string() == id) {
-                            model.messages.remove(index);
-                        }
-                    }
-                    _ => { }
+                let message = Message { id, name, author, points, problems };
+                if let Some(activity) = team_activity(model, mutation_type.as_str(), &message) {
+                    model.activity.push(activity);
                 }
+                apply_book_change(model, mutation_type.as_str(), message);
             }
         }
         Msg::WebSocketClosed(close_event) => {
@@ -424,23 +972,503 @@ fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
         //
         // Handles input change state
         //
-        Msg::InputTextNameChanged(input_text) => {
-            model.input_text_name = input_text;
+        Msg::PlayerTeamChanged(team_id) => {
+            model.hints.clear();
+            if team_id.is_empty() {
+                model.player_team_id = None;
+                return;
+            }
+            model.player_team_id = Some(team_id.clone());
+            orders.perform_cmd(async {
+                Msg::HintsFetched(
+                    send_graphql_request(&QHints::build_query(q_hints::Variables { team_id })).await
+                )
+            });
+        }
+        //
+        // Challenges & flag submission
+        //
+        Msg::ChallengesFetched(Ok(GQLResponse {
+            data: Some(data), ..
+        })) => {
+            model.challenges = data.challenges.into_iter().map(|challenge| Challenge {
+                id: challenge.id,
+                letter: challenge.letter,
+                name: challenge.name,
+                category: challenge.category,
+                author: challenge.author,
+                points: challenge.points,
+                value: challenge.value,
+                description: challenge.description,
+                attachments: challenge.attachments.into_iter().map(|attachment| Attachment {
+                    id: attachment.id,
+                    name: attachment.name,
+                    size: attachment.size,
+                    sha256: attachment.sha256,
+                }).collect(),
+                prerequisites: challenge.prerequisites,
+                releases_at: challenge.releases_at,
+            }).collect();
+            // Their details are refetched once the countdown hits zero
+            let now = model.now;
+            for challenge in model.challenges.iter().filter(|challenge| !challenge.is_released(now)) {
+                model.release_refetches.entry(challenge.id.clone()).or_insert(0);
+            }
+            // The solves arrived first
+            if model.score_history_incomplete {
+                seed_score_history(model);
+            }
+        }
+        Msg::ChallengesFetched(error) => log!(error),
+        Msg::SolvesFetched(Ok(GQLResponse {
+            data: Some(data), ..
+        })) => {
+            for solve in data.solves {
+                record_solve(model, Solve {
+                    team_id: solve.team_id,
+                    challenge_id: solve.challenge_id,
+                    solved_at: solve.solved_at,
+                });
+            }
+            snapshot_scoreboard(model);
+            seed_score_history(model);
+        }
+        Msg::SolvesFetched(error) => log!(error),
+        Msg::FlagSubmittedClick(challenge_id, flag) => {
+            if !model.phase.map_or(false, EventPhase::submissions_open) {
+                return;
+            }
+            let team_id = match &model.player_team_id {
+                Some(team_id) if !flag.is_empty() => team_id.clone(),
+                _ => return,
+            };
+            let value = model.challenges.iter()
+                .find(|challenge| challenge.id == challenge_id)
+                .map_or(0, |challenge| challenge.value);
+            let index = model.submissions.len();
+            model.submissions.push(Submission {
+                challenge_id: challenge_id.clone(),
+                flag: flag.clone(),
+                submitted_at: js_sys::Date::now(),
+                value,
+                outcome: None,
+            });
+            orders.perform_cmd(async move {
+                Msg::FlagSubmitted(
+                    index,
+                    send_graphql_request(&MSubmitFlag::build_query(m_submit_flag::Variables {
+                        team_id,
+                        challenge_id,
+                        flag,
+                    }))
+                    .await,
+                )
+            });
+        }
+        Msg::FlagSubmitted(index, Ok(GQLResponse {
+            data: Some(data), ..
+        })) => {
+            let result = data.submit_flag;
+            let outcome = match result.status {
+                m_submit_flag::SubmissionStatus::CORRECT => SubmissionOutcome::Correct,
+                m_submit_flag::SubmissionStatus::INCORRECT => SubmissionOutcome::Incorrect,
+                m_submit_flag::SubmissionStatus::ALREADY_SOLVED => SubmissionOutcome::AlreadySolved,
+                m_submit_flag::SubmissionStatus::RATE_LIMITED => SubmissionOutcome::RateLimited(result.retry_after),
+                m_submit_flag::SubmissionStatus::Other(status) => SubmissionOutcome::Failed(status),
+            };
+            if outcome == SubmissionOutcome::Correct {
+                if let Some(submission) = model.submissions.get(index) {
+                    if let Some(challenge) = model.challenges.iter().find(|c| c.id == submission.challenge_id) {
+                        model.ledger.push(LedgerEntry {
+                            team_id: result.team.id.clone(),
+                            description: format!("Solved {} - {}", challenge.letter, challenge.name),
+                            points: submission.value,
+                            at: js_sys::Date::now(),
+                        });
+                    }
+                }
+                //
+                // Unlock the dependent challenges now, the subscription is silent while frozen
+                //
+                if let Some(submission) = model.submissions.get(index) {
+                    let solve = Solve {
+                        team_id: result.team.id.clone(),
+                        challenge_id: submission.challenge_id.clone(),
+                        solved_at: server_now(model),
+                    };
+                    // The subscription echo is deduplicated, report the solve in the feed from here
+                    let first_blood = !model.solves.iter().any(|s| s.challenge_id == solve.challenge_id);
+                    let activity = solve_activity(model, &solve, first_blood);
+                    if record_solve(model, solve) && model.frozen_scoreboard.is_none() {
+                        model.activity.push(activity);
+                    }
+                }
+                //
+                // Same path as a subscription "UPDATED" event
+                //
+                let team = result.team;
+                match team.points.parse() {
+                    Ok(points) => apply_book_change(model, "UPDATED", Message {
+                        id: team.id,
+                        name: team.name,
+                        author: team.author,
+                        points,
+                        problems: vec![
+                            Problem{ letter:"A".to_string() },
+                            Problem{ letter:"B".to_string() },
+                        ],
+                    }),
+                    // Keep the last known score rather than showing 0
+                    Err(_) => log!("Invalid points", team.points),
+                }
+            }
+            if let Some(submission) = model.submissions.get_mut(index) {
+                submission.outcome = Some(outcome);
+            }
+        }
+        Msg::FlagSubmitted(index, error) => {
+            log!(error);
+            if let Some(submission) = model.submissions.get_mut(index) {
+                submission.outcome = Some(SubmissionOutcome::Failed("Request failed".to_string()));
+            }
+        }
+        //
+        // Hints
+        //
+        Msg::HintsFetched(Ok(GQLResponse {
+            data: Some(data), ..
+        })) => {
+            model.hints = data.hints.into_iter().map(|hint| Hint {
+                id: hint.id,
+                challenge_id: hint.challenge_id,
+                cost: hint.cost,
+                content: if hint.unlocked { Some(hint.content.unwrap_or_default()) } else { None },
+            }).collect();
+        }
+        Msg::HintsFetched(error) => log!(error),
+        Msg::HintUnlockConfirmed(hint_id) => {
+            if !model.phase.map_or(false, EventPhase::submissions_open) {
+                return;
+            }
+            let team_id = match &model.player_team_id {
+                Some(team_id) => team_id.clone(),
+                None => return,
+            };
+            orders.perform_cmd(async {
+                Msg::HintUnlocked(
+                    send_graphql_request(&MUnlockHint::build_query(m_unlock_hint::Variables {
+                        team_id,
+                        hint_id,
+                    }))
+                    .await,
+                )
+            });
+        }
+        Msg::HintUnlocked(Ok(GQLResponse {
+            data: Some(data), ..
+        })) => {
+            let result = data.unlock_hint;
+            let already_unlocked = model.hints.iter()
+                .any(|hint| hint.id == result.hint.id && hint.content.is_some());
+            // Refused unlocks, e.g. not enough points, cost nothing
+            if result.hint.unlocked && !already_unlocked {
+                let challenge = model.challenges.iter().find(|c| c.id == result.hint.challenge_id);
+                model.ledger.push(LedgerEntry {
+                    team_id: result.team.id.clone(),
+                    description: match challenge {
+                        Some(challenge) => format!("Hint for {} - {}", challenge.letter, challenge.name),
+                        None => "Hint".to_string(),
+                    },
+                    points: -result.hint.cost,
+                    at: js_sys::Date::now(),
+                });
+            }
+            if let Some(hint) = model.hints.iter_mut().find(|hint| hint.id == result.hint.id) {
+                if result.hint.unlocked {
+                    hint.content = Some(result.hint.content.unwrap_or_default());
+                }
+            }
+            let team = result.team;
+            match team.points.parse() {
+                Ok(points) => apply_book_change(model, "UPDATED", Message {
+                    id: team.id,
+                    name: team.name,
+                    author: team.author,
+                    points,
+                    problems: vec![
+                        Problem{ letter:"A".to_string() },
+                        Problem{ letter:"B".to_string() },
+                    ],
+                }),
+                // Keep the last known score rather than showing 0
+                Err(_) => log!("Invalid points", team.points),
+            }
+        }
+        Msg::HintUnlocked(error) => log!(error),
+        //
+        // Attachments are served through short lived urls issued per team
+        //
+        Msg::AttachmentDownloadClick(attachment_id) => {
+            let team_id = match &model.player_team_id {
+                Some(team_id) => team_id.clone(),
+                None => return,
+            };
+            orders.perform_cmd(async {
+                Msg::AttachmentUrlFetched(
+                    send_graphql_request(&QAttachmentUrl::build_query(q_attachment_url::Variables {
+                        team_id,
+                        attachment_id,
+                    }))
+                    .await,
+                )
+            });
         }
-        Msg::InputTextAuthorChanged(input_text) => {
-            model.input_text_author = input_text;
+        Msg::AttachmentUrlFetched(Ok(GQLResponse {
+            data: Some(data), ..
+        })) => {
+            Url::go_and_load_with_str(data.attachment_url);
         }
-        Msg::InputTextPointsChanged(input_text) => {
-            model.input_text_points = input_text;
+        Msg::AttachmentUrlFetched(error) => log!(error),
+    }
+}
+
+//
+// Carry out an action returned by a page
+//
+fn perform(action: page::Action, orders: &mut impl Orders<Msg>) {
+    orders.send_msg(match action {
+        page::Action::SelectTeam(team_id) => Msg::PlayerTeamChanged(team_id),
+        page::Action::SubmitFlag { challenge_id, flag } => Msg::FlagSubmittedClick(challenge_id, flag),
+        page::Action::UnlockHint(hint_id) => Msg::HintUnlockConfirmed(hint_id),
+        page::Action::DownloadAttachment(attachment_id) => Msg::AttachmentDownloadClick(attachment_id),
+        page::Action::UpdateTeam { id, name, author, points } => Msg::BookUpdatedClick(id, name, author, points),
+        page::Action::DeleteTeam(id) => Msg::BookDeletedClick(id),
+        page::Action::ExportChart(kind) => Msg::ChartExportClick(kind),
+    });
+}
+
+//
+// Drop what the previous player did on this browser, e.g. the submitted flags
+//
+fn forget_player(model: &mut Model) {
+    model.player_team_id = None;
+    model.hints.clear();
+    model.ledger.clear();
+    model.submissions.clear();
+    match &mut model.page {
+        Page::Challenges(page_model) => page::challenges::player_changed(page_model),
+        Page::Challenge(page_model) => page::challenge::player_changed(page_model),
+        _ => {}
+    }
+}
+
+//
+// Move the event to the phase matching the schedule and server clock
+//
+fn update_phase(model: &mut Model, orders: &mut impl Orders<Msg>) {
+    let phase = match &model.event {
+        Some(event) => EventPhase::at(event, model.now),
+        None => return,
+    };
+    if model.phase == Some(phase) {
+        return;
+    }
+    log!("Event phase:", phase.label());
+    // Challenges are only served once the event started
+    if model.phase == Some(EventPhase::Scheduled) && phase.challenges_visible() {
+        fetch_challenges(orders);
+    }
+    // The subscriptions were silent while frozen, the final standings have to be fetched
+    if model.phase == Some(EventPhase::Frozen) {
+        fetch_books(orders);
+        fetch_solves(orders);
+    }
+    model.phase = Some(phase);
+    snapshot_scoreboard(model);
+}
+
+//
+// Keep the standings of the moment the scoreboard froze. The server answers
+// queries with frozen standings too, so fetched data replaces the snapshot.
+//
+fn snapshot_scoreboard(model: &mut Model) {
+    model.frozen_scoreboard = match model.phase {
+        Some(EventPhase::Frozen) => Some(ScoreboardSnapshot {
+            messages: model.messages.clone(),
+            solves: model.solves.clone(),
+        }),
+        _ => None,
+    };
+}
+
+//
+// Teams on the public standings, frozen or live
+//
+fn standings_teams(model: &Model) -> &[Message] {
+    match &model.frozen_scoreboard {
+        Some(snapshot) => &snapshot.messages,
+        None => &model.messages,
+    }
+}
+
+//
+// Solves on the public standings, frozen or live. Unlocking challenges
+// uses the live solves.
+//
+fn standings_solves(model: &Model) -> &[Solve] {
+    match &model.frozen_scoreboard {
+        Some(snapshot) => &snapshot.solves,
+        None => &model.solves,
+    }
+}
+
+//
+// Store a solve once, both the initial query and the subscription report it.
+// Challenges depending on it unlock on the next render.
+// Returns false if the solve was already known.
+//
+fn record_solve(model: &mut Model, solve: Solve) -> bool {
+    let known = model.solves.iter()
+        .any(|s| s.team_id == solve.team_id && s.challenge_id == solve.challenge_id);
+    if !known {
+        model.solves.push(solve);
+    }
+    !known
+}
+
+fn solve_activity(model: &Model, solve: &Solve, first_blood: bool) -> Activity {
+    let challenge = match model.challenges.iter().find(|c| c.id == solve.challenge_id) {
+        Some(challenge) => format!("{} - {}", challenge.letter, challenge.name),
+        None => solve.challenge_id.clone(),
+    };
+    let team = team_name(model, &solve.team_id);
+    Activity {
+        kind: if first_blood { ActivityKind::FirstBlood } else { ActivityKind::Solve },
+        at: solve.solved_at,
+        text: if first_blood {
+            format!("First blood! {} solved {}", team, challenge)
+        } else {
+            format!("{} solved {}", team, challenge)
+        },
+    }
+}
+
+//
+// Feed entry of a team change from the subscription, score updates are
+// already covered by the solves
+//
+fn team_activity(model: &Model, mutation_type: &str, message: &Message) -> Option<Activity> {
+    let known = model.messages.iter().find(|m| m.id == message.id);
+    let (kind, text) = match (mutation_type, known) {
+        ("CREATED", _) => (ActivityKind::NewTeam, format!("{} joined", message.name)),
+        ("DELETED", Some(known)) => (ActivityKind::AdminChange, format!("{} was removed", known.name)),
+        ("UPDATED", Some(known)) if known.name != message.name => {
+            (ActivityKind::AdminChange, format!("{} was renamed to {}", known.name, message.name))
         }
+        ("UPDATED", Some(known)) if known.author != message.author => {
+            (ActivityKind::AdminChange, format!("{} changed author to {}", message.name, message.author))
+        }
+        _ => return None,
+    };
+    Some(Activity { kind, at: server_now(model), text })
+}
+
+//
+// Current server time, also while the ticker is stopped and `model.now` lags behind
+//
+fn server_now(model: &Model) -> f64 {
+    model.time_sync.server_time(js_sys::Date::now())
+}
+
+fn is_solved(model: &Model, team_id: &str, challenge_id: &str) -> bool {
+    model.solves.iter().any(|solve| solve.team_id == team_id && solve.challenge_id == challenge_id)
+}
+
+//
+// Apply a team (book) change coming from the subscription or from
+// our own mutations
+//
+fn apply_book_change(model: &mut Model, mutation_type: &str, message: Message) {
+    let team_id = message.id.clone();
+    match mutation_type {
+        "CREATED" => {
+            model.messages.push(message);
+        }
+        "UPDATED" => {
+            if let Some(index) = model.messages.iter().position(|m| m.id == message.id) {
+                model.messages[index] = message;
+            }
+        }
+        "DELETED" => {
+            if let Some(index) = model.messages.iter().position(|m| m.id == message.id) {
+                model.messages.remove(index);
+            }
+            if let Page::Scoreboard(page_model) = &mut model.page {
+                page::scoreboard::team_deleted(page_model, &team_id);
+            }
+        }
+        _ => { }
+    }
+    sample_scores(model);
+}
+
+//
+// Rebuild the score graph from the solves, live samples continue on top.
+// Hint costs and earlier challenge values are unknown here, the next live
+// sample brings the exact totals.
+//
+fn seed_score_history(model: &mut Model) {
+    let solves: Vec<(Id, f64, i64)> = standings_solves(model).iter()
+        .sorted_by(|a, b| a.solved_at.partial_cmp(&b.solved_at).unwrap())
+        .filter_map(|solve| {
+            let challenge = model.challenges.iter().find(|challenge| challenge.id == solve.challenge_id)?;
+            Some((solve.team_id.clone(), solve.solved_at, challenge.value))
+        })
+        .collect();
+    model.score_history_incomplete = solves.len() < standings_solves(model).len();
+    let start = match (&model.event, solves.first()) {
+        (Some(event), _) => event.starts_at,
+        (None, Some((_, solved_at, _))) => *solved_at,
+        (None, None) => return,
+    };
+    let teams: Vec<Id> = standings_teams(model).iter().map(|message| message.id.clone()).collect();
+    model.score_history.rebuild(
+        start,
+        &teams,
+        solves.iter().map(|(team_id, solved_at, points)| (team_id, *solved_at, *points)),
+    );
+    //
+    // No live samples follow once the standings stopped changing, close with the exact totals
+    //
+    let closed_at = match (&model.event, model.phase) {
+        (Some(event), Some(EventPhase::Frozen)) => event.freezes_at.unwrap_or(event.ends_at),
+        (Some(event), Some(EventPhase::Ended)) | (Some(event), Some(EventPhase::Archived)) => event.ends_at,
+        _ => return,
+    };
+    let closed_at = model.score_history.last_sample_at().map_or(closed_at, |last| last.max(closed_at));
+    let scores: Vec<(Id, i64)> = standings_teams(model).iter().map(|message| (message.id.clone(), message.points)).collect();
+    model.score_history.sample(closed_at, scores.iter().map(|(team_id, points)| (team_id, *points)));
+}
+
+//
+// Add the current team totals to the score graph, not while the scoreboard is frozen
+// or the ticker is stopped, `model.now` does not advance then
+//
+fn sample_scores(model: &mut Model) {
+    if !model.phase.map_or(false, EventPhase::scoreboard_live) || model.timer_handle.is_none() {
+        return;
     }
+    let scores = model.messages.iter().map(|message| (&message.id, message.points));
+    model.score_history.sample(model.now, scores);
 }
 
 // ------ ------
 //     View
 // ------ ------
 fn view(model: &Model) -> Node<Msg> {
-    let mut clock = shared::Clock::new();
+    if model.kiosk.is_some() {
+        return view_kiosk(model);
+    }
     div![C!["overflow-auto"],
         style! {
             St::BackgroundColor => "#282a36",
@@ -449,246 +1477,438 @@ fn view(model: &Model) -> Node<Msg> {
         //
         // HEADER
         //
-        nav![C!["navbar navbar-expand-lg navbar-dark bg-dark"],
-            a![C!["navbar-brand"], "LOGO",
-                style!{
-                    St::Color => "#50fa7b",
-                }
-            ],
-            button![C!["navbar-toggler"],
-                attrs! { At::Type => "button", },
-                span![C!["navbar-toggler-icon"]]
-            ],
-            div![C!["collapse navbar-collapse"],
-                ul![C!["navbar-nav mr-auto"],
-                    li![C!["nav-item active"],
-                        a![C!["nav-link"], "Home"]
-                    ]
-                ],
-                form![C!["form-inline my-2 my-lg-0"],
-                    button![C!["btn btn-secondary mr-sm-2"], "Sign Up",
-                        attrs! { At::Type => "button", },
-                        style! {
-                            St::BackgroundColor => "#9580ff"
-                        }
-                    ],
-                    button![C!["btn btn-secondary mr-sm-2"], "Log in",
-                        attrs! { At::Type => "button" },
-                        style! {
-                            St::BackgroundColor => "#50fa7b"
-                        }
-                    ],
-                ]
-            ],
-        ],
-        //
-        // Create Book
-        //
+        view_navbar(model),
         div![C!["container"],
-            h3![C!["description"], "Create Book",
-                style!{
-                    St::Color => "#50fa7b"
-                },
-            ],
-            form![
-                div![C!["form-group"],
-                    label!["name"],
-                    style![
-                        St::Color => "#9580ff",
-                    ],
-                    input![C!["form-control"],
-                        id!("text_input_name"),
-                        attrs! {
-                            At::Type => "text",
-                            At::Value => model.input_text_name,
-                            At::Placeholder => "name",
-                        },
-                        input_ev(Ev::Input, Msg::InputTextNameChanged),
+            div![C!["row mt-3"],
+                //
+                // # of websocket messages
+                //
+                div![C!["col-sm"],
+                    p![format!("messages: {}", model.messages.len()),
+                        style![
+                            St::Color => "#FFFFFF",
+                        ],
                     ],
                 ],
-                div![C!["form-group"],
-                    label!["author"],
-                    style![
-                        St::Color => "#9580ff",
-                    ],
-                    input![C!["form-control"],
-                        id!("text_input_author"),
-                        attrs! {
-                            At::Type => "text",
-                            At::Value => model.input_text_author,
-                            At::Placeholder => "author",
-                        },
-                        input_ev(Ev::Input, Msg::InputTextAuthorChanged),
-                    ],
+                //
+                // Countdown from the synced server clock
+                //
+                div![C!["col-sm"],
+                    view_countdown(model),
                 ],
-                div![C!["form-group"],
-                    label!["points"],
-                    style![
-                        St::Color => "#9580ff",
-                    ],
-                    input![C!["form-control"],
-                        id!("text_input_points"),
-                        attrs! {
-                            At::Type => "number",
-                            At::Value => model.input_text_points,
-                            At::Placeholder => "points",
+                //
+                // Interval update
+                //
+                div![C!["col-sm"],
+                    view_progress(model),
+                ]
+            ],
+            match &model.page {
+                Page::Scoreboard(page_model) => page::scoreboard::view(page_model, model).map_msg(Msg::Scoreboard),
+                Page::Challenges(page_model) => page::challenges::view(page_model, model).map_msg(Msg::Challenges),
+                Page::Challenge(page_model) => page::challenge::view(page_model, model).map_msg(Msg::Challenge),
+                Page::Team(page_model) => page::team::view(page_model, model).map_msg(Msg::Team),
+                Page::Profile => page::profile::view(model).map_msg(Msg::Profile),
+                Page::Login(page_model) => page::login::view(page_model, &model.base_url).map_msg(Msg::Login),
+                Page::Admin(page_model) => div![
+                    page::admin::view(page_model).map_msg(Msg::Admin),
+                    view_ticker_controls(model),
+                    view_challenge_graph(model),
+                ],
+                Page::NotFound => div![
+                    h3![C!["description"], "Page not found",
+                        style!{
+                            St::Color => "#50fa7b"
                         },
-                        input_ev(Ev::Input, Msg::InputTextPointsChanged),
                     ],
+                    a![attrs! { At::Href => Urls::new(&model.base_url).scoreboard() }, "Back to the scoreboard"],
                 ],
+            },
+        ],
+    ]
+}
+
+fn view_navbar(model: &Model) -> Node<Msg> {
+    let urls = || Urls::new(&model.base_url);
+    let nav_item = |label: &str, url: Url, active: bool| li![C!["nav-item", IF!(active => "active")],
+        a![C!["nav-link"], attrs! { At::Href => url }, label]
+    ];
+    nav![C!["navbar navbar-expand-lg navbar-dark bg-dark"],
+        a![C!["navbar-brand"], "LOGO",
+            attrs! { At::Href => urls().scoreboard() },
+            style!{
+                St::Color => "#50fa7b",
+            }
+        ],
+        button![C!["navbar-toggler"],
+            attrs! { At::Type => "button", },
+            span![C!["navbar-toggler-icon"]]
+        ],
+        div![C!["collapse navbar-collapse"],
+            ul![C!["navbar-nav mr-auto"],
+                nav_item("Scoreboard", urls().scoreboard(), matches!(model.page, Page::Scoreboard(_) | Page::Team(_))),
+                nav_item("Challenges", urls().challenges(), matches!(model.page, Page::Challenges(_) | Page::Challenge(_))),
+                nav_item("Profile", urls().profile(), matches!(model.page, Page::Profile)),
+                nav_item("Admin", urls().admin(), matches!(model.page, Page::Admin(_))),
             ],
-            div![C!["container"],
-                div![C!["row"],
-                    div![C!["col-sm"],
-                        // Button Click to trigger CREATE function
-                        button![C!["btn"], "Create Book",
-                            ev(Ev::Click, {
-                                let name = model.input_text_name.to_owned();
-                                let author = model.input_text_author.to_owned();
-                                let points = model.input_text_points.to_owned();
-                                move |_| Msg::BookCreatedClick(name.to_string(), author.to_string(), points.to_string())
-                            }),
-                            style! {
-                                St::BackgroundColor => "#50fa7b",
-                            }
-                        ],
-                    ],
-                    //
-                    // # of websocket messages
-                    //
-                    div![C!["col-sm"],
-                        p![format!("messages: {}", model.messages.len()),
-                            style![
-                                St::Color => "#FFFFFF",
-                            ],
-                        ],
-                    ],
-                    //
-                    // Interval update
-                    //
-                    div![C!["col-sm"],
-                        p![C!["progress"],
-                            div![C!["progress-bar progress-bar-striped progress-bar-animated"],
-                                style![
-                                    St::Width => format!("{}%", model.seconds)
-                                ],
-                                format!("{}%", model.seconds)
-                            ]
-                        ],
-                    ]
+            form![C!["form-inline my-2 my-lg-0"],
+                view_time_zone_select(model),
+                view_user_menu(model),
+            ]
+        ],
+    ]
+}
+
+//
+// Sign up and log in links, the player's name with a sign out menu once signed in
+//
+fn view_user_menu(model: &Model) -> Node<Msg> {
+    let urls = || Urls::new(&model.base_url);
+    let session = match &model.session {
+        Some(session) => session,
+        None => {
+            return div![
+                a![C!["btn btn-secondary mr-sm-2"], "Sign Up",
+                    attrs! { At::Href => urls().sign_up() },
+                    style! {
+                        St::BackgroundColor => "#9580ff"
+                    }
+                ],
+                a![C!["btn btn-secondary mr-sm-2"], "Log in",
+                    attrs! { At::Href => urls().login() },
+                    style! {
+                        St::BackgroundColor => "#50fa7b"
+                    }
                 ],
+            ];
+        }
+    };
+    div![C!["dropdown"],
+        button![C!["btn btn-secondary dropdown-toggle mr-sm-2"], &session.name,
+            attrs! { At::Type => "button" },
+            ev(Ev::Click, |_| Msg::UserMenuToggled),
+            style! {
+                St::BackgroundColor => "#9580ff"
+            }
+        ],
+        div![C!["dropdown-menu dropdown-menu-right", IF!(model.user_menu_open => "show")],
+            a![C!["dropdown-item"], "Profile", attrs! { At::Href => urls().profile() }],
+            button![C!["dropdown-item"], "Sign out",
+                attrs! { At::Type => "button" },
+                ev(Ev::Click, |_| Msg::SignOutClick),
             ],
-            div![
-                // The class required by GitHub styles. See `index.html`.
-                C!["markdown-body"],
-                style![
-                    St::Color => "#9580ff",
+        ],
+    ]
+}
+
+//
+// Wall display: no navbar or forms, large scoreboard paging by itself
+//
+fn view_kiosk(model: &Model) -> Node<Msg> {
+    let kiosk = match &model.kiosk {
+        Some(kiosk) => kiosk,
+        None => return empty![],
+    };
+    let team_count = standings_teams(model).len();
+    div![C!["overflow-hidden"],
+        style! {
+            St::BackgroundColor => "#282a36",
+            St::Height => vh(100),
+        },
+        div![C!["container-fluid"],
+            div![C!["row pt-3"],
+                div![C!["col-lg-6"],
+                    view_countdown(model),
+                    style! {
+                        St::FontSize => em(1.5),
+                    }
+                ],
+                div![C!["col-lg-6"],
+                    view_progress(model),
                 ],
-                md!( plot(
-                        model.graph.clone(),
-                        Config::default().with_offset(10).with_height(10)
-                    ).as_str()
-                ),
             ],
-            //
-            // Scoring
-            //
-            div![
-                table![C!["table table-striped table-bordered table-dark"],
-                    //
-                    // Table headers
-                    //
-                    thead![
-                        tr![
-                            th![ C!["text-center"], attrs! { At::Scope => "col", }, "#" ],
-                            th![ C!["text-center"], attrs! { At::Scope => "col", }, "ID" ],
-                            th![ C!["text-center"], attrs! { At::Scope => "col", }, "Name" ],
-                            th![ C!["text-center"], attrs! { At::Scope => "col", }, "Author" ],
-                            th![ C!["text-center"], attrs! { At::Scope => "col", }, "Points" ],
-                            model.problems.iter().map(| problem |
-                                {
-                                    th![ C!["text-center"], attrs! { At::Scope => "col", }, a![&problem.letter],br![],span!["100"] ]
-                                }
-                            ),
-                            th![ C!["text-center"], attrs! { At::Scope => "col", }, "Actions" ],
-                            
-                            // th![ C!["text-center"], attrs! { At::Scope => "col", }, a!["A"],br![],span!["100"] ],
-                            // th![ C!["text-center"], attrs! { At::Scope => "col", }, a!["B"],br![],span!["200"] ],
-                            // th![ C!["text-center"], attrs! { At::Scope => "col", }, a!["C"],br![],span!["300"] ],
-                            // th![ C!["text-center"], attrs! { At::Scope => "col", }, a!["D"],br![],span!["400"] ],
-                            // th![ C!["text-center"], attrs! { At::Scope => "col", }, a!["E"],br![],span!["500"] ],
-                        ]
+            div![C!["row"],
+                div![C!["col-lg-7"],
+                    page::scoreboard::view_standings(model).map_msg(Msg::Scoreboard),
+                    p![C!["text-right"],
+                        format!("Page {} / {}", kiosk.page(team_count) + 1, kiosk.page_count(team_count)),
+                        style! {
+                            St::Color => "#6272a4",
+                        }
                     ],
-                    tbody![
-                        //
-                        // Sort rows in table in decending order by 'points' key/value
-                        // Label new position
-                        //
-                        // GOTCHA 1: using enumerate() after sorted_by well create
-                        // sorted index
-                        //
-                        // GOTCHA 2: enumerate() creates a tuple (index, &thing)
-                        // index = 0, &thing = 1
-                        //
-                        // Position/Index = some.0
-                        // Key/Value = some.1.key
-                        //
-                        model.messages.iter().sorted_by(|a, b| Ord::cmp(&b.points, &a.points)).enumerate().map(| message |
-                            {
-                                clock.set_time_ms(model.seconds);
-                            tr![
-                                td![ C!["text-center"], attrs! { At::Scope => "col", }, format!("{}", message.0+1 ) ],
-                                td![ C!["text-center"], attrs! { At::Scope => "col", }, format!("{}", message.1.id) ],
-                                td![ C!["text-center"], attrs! { At::Scope => "col", }, format!("{}", message.1.name) ],
-                                td![ C!["text-center"], attrs! { At::Scope => "col", }, format!("{}", message.1.author) ],
-                                td![ C!["text-center"], attrs! { At::Scope => "col", }, format!("{}", message.1.points) ],
-                                td![ C!["text-center"], attrs! { At::Scope => "col", }, a![format!("{}", "Score")],br![],span![format!("{}", clock.get_time()) ]],
-                                td![ C!["text-center"], attrs! { At::Scope => "col", }, a![format!("{}", "Score")],br![],span![format!("{}", clock.get_time()) ]],
-                                td![ C!["text-center"], attrs! { At::Scope => "col", }, a![format!("{}", "Score")],br![],span![format!("{}", clock.get_time()) ]],
-                                td![ C!["text-center"], attrs! { At::Scope => "col", }, a![format!("{}", "Score")],br![],span![format!("{}", clock.get_time()) ]],
-                                td![ C!["text-center"], attrs! { At::Scope => "col", }, a![format!("{}", "Score")],br![],span![format!("{}", clock.get_time()) ]],
-                                td![ C!["text-center"], attrs! { At::Scope => "col", },
-                                    button![C!["btn"], format!("Update"),
-                                        attrs!{ At::Value => &message.1.id },
-                                        {
-                                            let id = message.1.id.clone();
-                                            let name = "test".to_string();
-                                            let author = "test".to_string();
-                                            let points = "100".to_string();
-                                            ev(Ev::Click, {
-                                                move |_| Msg::BookUpdatedClick(id, name, author, points)
-                                            })
-                                        },
-                                        style! {
-                                            St::BackgroundColor => "#50fa7b",
-                                        }
-                                    ],
-                                    button![C!["btn"], format!("Delete"),
-                                        attrs!{ At::Value => &message.1.id },
-                                        {
-                                            let id = message.1.id.clone();
-                                            ev(Ev::Click, {
-                                                move |_| Msg::BookDeletedClick(id)
-                                            })
-                                        },
-                                        style! {
-                                            St::BackgroundColor => "#50fa7b",
-                                        }
-                                    ],
-                                ],
-                                style![
-                                    St::Color => "#FFFFFF",
-                                ],
-                            ]
-                            },
-                        ),
-                    ]
+                    style! {
+                        St::FontSize => em(1.6),
+                    }
+                ],
+                div![C!["col-lg-5"],
+                    score_chart(model, model.score_history.top_teams(kiosk.top_teams)),
                 ],
+            ],
+        ],
+    ]
+}
+
+fn view_ticker_controls(model: &Model) -> Node<Msg> {
+    let state = if model.ticker_paused {
+        "paused"
+    } else if model.timer_handle.is_none() {
+        "paused while the tab is hidden"
+    } else {
+        "running"
+    };
+    div![
+        h3![C!["description"], "Ticker",
+            style!{
+                St::Color => "#50fa7b"
+            },
+        ],
+        p![format!("The ticker is {}.", state),
+            style! {
+                St::Color => "#FFFFFF",
+            }
+        ],
+        if model.ticker_paused {
+            button![C!["btn mr-sm-2"], "Resume",
+                attrs! { At::Type => "button" },
+                ev(Ev::Click, |_| Msg::TickerResumeClick),
+                style! {
+                    St::BackgroundColor => "#50fa7b",
+                }
+            ]
+        } else {
+            button![C!["btn mr-sm-2"], "Pause",
+                attrs! { At::Type => "button" },
+                ev(Ev::Click, |_| Msg::TickerPauseClick),
                 style! {
-                    St::MarginTop => px(15),
+                    St::BackgroundColor => "#ffb86c",
                 }
+            ]
+        },
+        button![C!["btn"], "Reset",
+            attrs! { At::Type => "button" },
+            ev(Ev::Click, |_| Msg::TickerResetClick),
+            style! {
+                St::BackgroundColor => "#ff5555",
+            }
+        ],
+    ]
+}
+
+//
+// Absolute time in the zone selected by the viewer
+//
+fn format_time(model: &Model, ms: f64) -> String {
+    let event_time_zone = model.event.as_ref().map(|event| event.time_zone.as_str());
+    time_zone::format(ms, model.time_zone.iana_name(event_time_zone).as_deref(), false)
+}
+
+fn format_time_of_day(model: &Model, ms: f64) -> String {
+    let event_time_zone = model.event.as_ref().map(|event| event.time_zone.as_str());
+    time_zone::format(ms, model.time_zone.iana_name(event_time_zone).as_deref(), true)
+}
+
+fn view_time_zone_select(model: &Model) -> Node<Msg> {
+    let event_time_zone = model.event.as_ref().map(|event| event.time_zone.as_str());
+    select![C!["custom-select custom-select-sm mr-sm-2"],
+        attrs! { At::Title => "Time zone" },
+        TimeZoneChoice::ALL.iter().map(|choice| {
+            let label = match choice {
+                TimeZoneChoice::Local => "Local time".to_string(),
+                TimeZoneChoice::Utc => "UTC".to_string(),
+                TimeZoneChoice::Event => format!("Event time ({})", event_time_zone.unwrap_or("unknown")),
+            };
+            option![
+                attrs! { At::Value => choice.key() },
+                IF!(*choice == model.time_zone => attrs! { At::Selected => AtValue::None }),
+                label
+            ]
+        }),
+        input_ev(Ev::Change, Msg::TimeZoneChanged),
+    ]
+}
+
+//
+// Fraction of the event elapsed, stops at the end
+//
+fn view_progress(model: &Model) -> Node<Msg> {
+    let (event, phase) = match (&model.event, model.phase) {
+        (Some(event), Some(phase)) => (event, phase),
+        _ => return empty![],
+    };
+    let duration = (event.ends_at - event.starts_at).max(1.0);
+    let now = model.now.max(event.starts_at).min(event.ends_at);
+    let percent = (now - event.starts_at) / duration * 100.0;
+    let remaining = shared::Clock::from_ms((event.ends_at - now) as i64);
+
+    let class = match phase {
+        EventPhase::Scheduled => "progress-bar",
+        EventPhase::Running if event.ends_at - now <= FINAL_STRETCH_MS =>
+            "progress-bar progress-bar-striped progress-bar-animated bg-warning",
+        EventPhase::Running => "progress-bar progress-bar-striped progress-bar-animated",
+        EventPhase::Frozen => "progress-bar progress-bar-striped progress-bar-animated bg-info",
+        EventPhase::Ended | EventPhase::Archived => "progress-bar bg-secondary",
+    };
+    let label = match phase {
+        EventPhase::Ended | EventPhase::Archived => "Ended".to_string(),
+        _ => format!("{} left", remaining.get_time()),
+    };
+    p![C!["progress"],
+        attrs! { At::Title => format!("{:.0}% elapsed", percent) },
+        div![C![class],
+            attrs! {
+                At::Custom("role".into()) => "progressbar",
+                At::Custom("aria-valuenow".into()) => format!("{:.0}", percent),
+                At::Custom("aria-valuemin".into()) => "0",
+                At::Custom("aria-valuemax".into()) => "100",
+            },
+            style![
+                St::Width => format!("{:.2}%", percent),
+                St::Overflow => "visible",
             ],
+            label
+        ]
+    ]
+}
+
+fn team_name(model: &Model, team_id: &Id) -> String {
+    match model.messages.iter().find(|message| &message.id == team_id) {
+        Some(message) => message.name.clone(),
+        None => team_id.to_string(),
+    }
+}
+
+//
+// A line needs two samples, e.g. the start of the event and a solve
+//
+fn score_graph_plottable(model: &Model) -> bool {
+    model.score_history.times().len() >= 2
+}
+
+fn score_chart<Ms: 'static>(model: &Model, team_ids: Vec<&Id>) -> Node<Ms> {
+    let times = model.score_history.times();
+    if !score_graph_plottable(model) {
+        return empty![];
+    }
+    let series = team_ids
+        .into_iter()
+        .filter_map(|team_id| {
+            let scores = model.score_history.series(team_id)?;
+            Some(chart::Series {
+                name: team_name(model, team_id),
+                points: times.iter().copied().zip(scores.iter().copied()).collect(),
+            })
+        })
+        .collect();
+    let x_format = |ms: f64| format_time_of_day(model, ms);
+    chart::line_chart(&chart::LineChart {
+        title: "Score over time".to_string(),
+        y_label: "points".to_string(),
+        series,
+        x_format: &x_format,
+    })
+}
+
+//
+// Disabled while the chart has nothing to draw
+//
+fn view_export_button<Ms: 'static>(kind: ChartKind, available: bool, on_click: fn(ChartKind) -> Ms) -> Node<Ms> {
+    button![C!["btn btn-sm btn-outline-secondary ml-sm-2"],
+        attrs! {
+            At::Type => "button",
+            At::Title => if available { format!("Download {}", kind.file_name()) } else { "Nothing to export yet".to_string() },
+            At::Disabled => (!available).as_at_value(),
+        },
+        "Export SVG",
+        ev(Ev::Click, move |_| on_click(kind)),
+    ]
+}
+
+//
+// Footer of exported charts
+//
+fn export_metadata(model: &Model) -> Vec<String> {
+    let mut metadata = vec![];
+    if let Some(event) = &model.event {
+        metadata.push(format!("{}, {} to {}", event.name, format_time(model, event.starts_at), format_time(model, event.ends_at)));
+    }
+    if let Some(phase) = model.phase {
+        metadata.push(format!("Exported {} ({})", format_time(model, model.now), phase.label()));
+    } else {
+        metadata.push(format!("Exported {}", format_time(model, model.now)));
+    }
+    metadata
+}
+
+fn view_countdown(model: &Model) -> Node<Msg> {
+    let (event, phase) = match (&model.event, model.phase) {
+        (Some(event), Some(phase)) => (event, phase),
+        _ => return empty![],
+    };
+    // Negative once the event is over, time since the end
+    let clock = shared::Clock::from_ms((phase.next_change(event).unwrap_or(event.ends_at) - model.now) as i64);
+    let text = match phase {
+        EventPhase::Scheduled => format!("{} starts in {}", event.name, clock.get_time()),
+        EventPhase::Running if event.freezes_at.map_or(false, |freezes_at| freezes_at < event.ends_at) =>
+            format!("{} freezes in {}", event.name, clock.get_time()),
+        EventPhase::Running | EventPhase::Frozen => format!("{} ends in {}", event.name, clock.get_time()),
+        EventPhase::Ended => format!("{} has ended", event.name),
+        EventPhase::Archived => format!("{} is archived", event.name),
+    };
+    div![
+        p![C!["mb-0"],
+            span![C!["badge badge-secondary mr-sm-2"], phase.label()],
+            text,
+            attrs! { At::Title => clock.get_time_verbose() },
+        ],
+        small![
+            format!("{} - {}", format_time(model, event.starts_at), format_time(model, event.ends_at)),
         ],
+        style![
+            St::Color => "#FFFFFF",
+        ],
+    ]
+}
+
+//
+// Admin check of the prerequisite graph
+//
+fn view_challenge_graph(model: &Model) -> Node<Msg> {
+    let errors = challenge_graph::validate(&model.challenges);
+    let letter = |id: &Id| match model.challenges.iter().find(|c| &c.id == id) {
+        Some(challenge) => challenge.letter.clone(),
+        None => id.to_string(),
+    };
+    div![
+        h3![C!["description"], "Unlock graph",
+            style!{
+                St::Color => "#50fa7b"
+            },
+        ],
+        ul![C!["list-unstyled"],
+            model.challenges.iter().map(|challenge| li![
+                format!("{} - {}", challenge.letter, challenge.name),
+                IF!(!challenge.prerequisites.is_empty() =>
+                    format!(" requires {}", challenge.prerequisites.iter().map(letter).join(", "))
+                ),
+            ]),
+            style! {
+                St::Color => "#FFFFFF",
+            }
+        ],
+        if errors.is_empty() {
+            div![C!["alert alert-success"], "No cycles or unknown prerequisites."]
+        } else {
+            div![C!["alert alert-danger"],
+                ul![C!["mb-0"],
+                    errors.iter().map(|error| li![match error {
+                        challenge_graph::GraphError::UnknownPrerequisite { challenge, prerequisite } =>
+                            format!("{} requires unknown challenge {}", letter(challenge), prerequisite),
+                        challenge_graph::GraphError::Cycle(cycle) =>
+                            format!("Cycle: {}", cycle.iter().map(letter).join(" -> ")),
+                    }]),
+                ],
+            ]
+        },
     ]
 }
 
@@ -0,0 +1,114 @@
+use pulldown_cmark::{html, Event, Options, Parser, Tag};
+use seed::prelude::*;
+
+// Link schemes that are allowed to end up in an `href` or `src`
+const ALLOWED_SCHEMES: [&str; 3] = ["http", "https", "mailto"];
+
+///
+/// Render admin-authored Markdown into nodes.
+///
+/// Unlike `md!`, raw HTML is dropped and links or images pointing to
+/// anything other than http(s), mailto or a relative url are removed.
+///
+/// * `markdown` - Markdown source to render.
+pub fn view<Ms>(markdown: &str) -> Vec<Node<Ms>> {
+    Node::from_html(&to_safe_html(markdown))
+}
+
+///
+/// Convert Markdown to HTML without raw HTML and unsafe urls.
+///
+/// * `markdown` - Markdown source to convert.
+pub fn to_safe_html(markdown: &str) -> String {
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_FOOTNOTES;
+
+    let events = Parser::new_ext(markdown, options).filter(|event| match event {
+        Event::Html(_) => false,
+        // Drop only the tag, link text and image alt text are kept as plain text
+        Event::Start(Tag::Link(_, url, _))
+        | Event::End(Tag::Link(_, url, _))
+        | Event::Start(Tag::Image(_, url, _))
+        | Event::End(Tag::Image(_, url, _)) => is_safe_url(url),
+        _ => true,
+    });
+
+    let mut html_text = String::new();
+    html::push_html(&mut html_text, events);
+    html_text
+}
+
+///
+/// Check that a url is relative or uses an allowed scheme.
+///
+/// * `url` - Link destination or image source.
+fn is_safe_url(url: &str) -> bool {
+    // Browsers ignore whitespace and control characters inside the scheme,
+    // e.g. "java\tscript:" is still a script url.
+    let url: String = url
+        .chars()
+        .filter(|c| !c.is_whitespace() && !c.is_control())
+        .collect::<String>()
+        .to_lowercase();
+
+    match url.find(|c| c == ':' || c == '/' || c == '?' || c == '#') {
+        Some(index) if url[index..].starts_with(':') => {
+            ALLOWED_SCHEMES.contains(&&url[..index])
+        }
+        // No scheme, the url is relative to the current page
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drops_raw_html() {
+        let html = to_safe_html("<script>alert(1)</script>\n\nText with <img src=x onerror=alert(1)> inline html");
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("<img"));
+        assert!(html.contains("Text with"));
+        assert!(html.contains("inline html"));
+    }
+
+    #[test]
+    fn drops_links_and_images_with_unsafe_schemes() {
+        for url in &["javascript:alert(1)", "JaVa\tscript:alert(1)", "data:text/html,<b>x</b>", "vbscript:msgbox(1)"] {
+            let html = to_safe_html(&format!("[link](<{url}>) ![image](<{url}>)", url = url));
+            assert!(!html.contains("<a"), "{}", html);
+            assert!(!html.contains("<img"), "{}", html);
+            // The text stays
+            assert!(html.contains("link"));
+        }
+    }
+
+    #[test]
+    fn keeps_safe_urls() {
+        let html = to_safe_html("[a](https://example.com) [b](files/readme.txt) [c](mailto:admin@example.com) ![d](/logo.png)");
+        assert!(html.contains("href=\"https://example.com\""));
+        assert!(html.contains("href=\"files/readme.txt\""));
+        assert!(html.contains("href=\"mailto:admin@example.com\""));
+        assert!(html.contains("src=\"/logo.png\""));
+    }
+
+    #[test]
+    fn keeps_tables_and_fenced_code() {
+        let html = to_safe_html("| a | b |\n|---|---|\n| 1 | 2 |\n\n```rust\nfn main() {}\n```\n");
+        assert!(html.contains("<table>"));
+        assert!(html.contains("<td>1</td>"));
+        assert!(html.contains("<pre><code class=\"language-rust\">fn main() {}"));
+    }
+
+    #[test]
+    fn checks_the_scheme_only() {
+        assert!(is_safe_url("HTTPS://example.com"));
+        assert!(is_safe_url("#section"));
+        assert!(is_safe_url("page?next=javascript:alert(1)"));
+        assert!(!is_safe_url(" javascript:alert(1)"));
+        assert!(!is_safe_url("java\nscript:alert(1)"));
+    }
+}
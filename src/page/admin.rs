@@ -0,0 +1,133 @@
+use crate::{m_create_book, send_graphql_request, GQLResponse, GraphQLQuery, MCreateBook};
+use seed::{prelude::*, *};
+
+// ------ ------
+//     Model
+// ------ ------
+
+pub struct Model {
+    input_text_name: String,
+    input_text_author: String,
+    input_text_points: String,
+}
+
+pub fn init() -> Model {
+    Model {
+        input_text_name: String::new(),
+        input_text_author: String::new(),
+        input_text_points: String::new(),
+    }
+}
+
+// ------ ------
+//    Update
+// ------ ------
+
+pub enum Msg {
+    InputTextNameChanged(String),
+    InputTextAuthorChanged(String),
+    InputTextPointsChanged(String),
+    BookCreatedClick,
+    BookCreated(fetch::Result<GQLResponse<m_create_book::ResponseData>>),
+}
+
+pub fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
+    match msg {
+        Msg::InputTextNameChanged(input_text) => {
+            model.input_text_name = input_text;
+        }
+        Msg::InputTextAuthorChanged(input_text) => {
+            model.input_text_author = input_text;
+        }
+        Msg::InputTextPointsChanged(input_text) => {
+            model.input_text_points = input_text;
+        }
+        //
+        // The new team arrives through the subscription
+        //
+        Msg::BookCreatedClick => {
+            let variables = m_create_book::Variables {
+                name: model.input_text_name.clone(),
+                author: model.input_text_author.clone(),
+                points: model.input_text_points.clone(),
+            };
+            orders.perform_cmd(async {
+                Msg::BookCreated(send_graphql_request(&MCreateBook::build_query(variables)).await)
+            });
+        }
+        Msg::BookCreated(Ok(GQLResponse {
+            data: Some(_), ..
+        })) => {
+            log!("Created Book");
+        }
+        Msg::BookCreated(error) => log!(error),
+    }
+}
+
+// ------ ------
+//     View
+// ------ ------
+
+pub fn view(model: &Model) -> Node<Msg> {
+    div![
+        h3![C!["description"], "Create Book",
+            style!{
+                St::Color => "#50fa7b"
+            },
+        ],
+        form![
+            div![C!["form-group"],
+                label!["name"],
+                style![
+                    St::Color => "#9580ff",
+                ],
+                input![C!["form-control"],
+                    id!("text_input_name"),
+                    attrs! {
+                        At::Type => "text",
+                        At::Value => model.input_text_name,
+                        At::Placeholder => "name",
+                    },
+                    input_ev(Ev::Input, Msg::InputTextNameChanged),
+                ],
+            ],
+            div![C!["form-group"],
+                label!["author"],
+                style![
+                    St::Color => "#9580ff",
+                ],
+                input![C!["form-control"],
+                    id!("text_input_author"),
+                    attrs! {
+                        At::Type => "text",
+                        At::Value => model.input_text_author,
+                        At::Placeholder => "author",
+                    },
+                    input_ev(Ev::Input, Msg::InputTextAuthorChanged),
+                ],
+            ],
+            div![C!["form-group"],
+                label!["points"],
+                style![
+                    St::Color => "#9580ff",
+                ],
+                input![C!["form-control"],
+                    id!("text_input_points"),
+                    attrs! {
+                        At::Type => "number",
+                        At::Value => model.input_text_points,
+                        At::Placeholder => "points",
+                    },
+                    input_ev(Ev::Input, Msg::InputTextPointsChanged),
+                ],
+            ],
+        ],
+        // Button Click to trigger CREATE function
+        button![C!["btn mb-3"], "Create Book",
+            ev(Ev::Click, |_| Msg::BookCreatedClick),
+            style! {
+                St::BackgroundColor => "#50fa7b",
+            }
+        ],
+    ]
+}
@@ -0,0 +1,435 @@
+use super::{view_team_select, Action};
+use crate::{
+    challenge_graph, format_time, format_time_of_day, is_solved, markdown, shared::Clock, standings_solves, team_name,
+    Attachment, Challenge, EventPhase, Hint, Id, Submission, SubmissionOutcome, Urls,
+};
+use itertools::Itertools;
+use seed::{prelude::*, *};
+
+// ------ ------
+//     Model
+// ------ ------
+
+//
+// A challenge with the player's inputs, the page of one challenge
+// and a card of the challenges page
+//
+pub struct Model {
+    challenge_id: Id,
+    input_text_flag: String,
+    // Hint waiting for the player to confirm its cost
+    hint_to_confirm: Option<Id>,
+}
+
+pub fn init(challenge_id: Id) -> Model {
+    Model {
+        challenge_id,
+        input_text_flag: String::new(),
+        hint_to_confirm: None,
+    }
+}
+
+///
+/// Forget the inputs of the previous player.
+///
+pub fn player_changed(model: &mut Model) {
+    model.input_text_flag.clear();
+    model.hint_to_confirm = None;
+}
+
+///
+/// Whether the current value has to be refetched when the subscription reports a solve.
+///
+/// * `challenge_id` - Challenge of the solve.
+pub fn solve_received(model: &Model, challenge_id: &str) -> bool {
+    model.challenge_id == challenge_id
+}
+
+// ------ ------
+//    Update
+// ------ ------
+
+pub enum Msg {
+    PlayerTeamChanged(Id),
+    InputTextFlagChanged(String),
+    FlagSubmittedClick,
+    HintUnlockClick(Id),
+    HintUnlockCancelled,
+    HintUnlockConfirmed,
+    AttachmentDownloadClick(Id),
+}
+
+///
+/// Returns what the app has to carry out, e.g. submit the flag.
+///
+/// * `msg` - Message of the page.
+/// * `model` - Model of the page.
+pub fn update(msg: Msg, model: &mut Model) -> Option<Action> {
+    match msg {
+        Msg::PlayerTeamChanged(team_id) => {
+            model.hint_to_confirm = None;
+            return Some(Action::SelectTeam(team_id));
+        }
+        Msg::InputTextFlagChanged(input_text) => {
+            model.input_text_flag = input_text;
+        }
+        Msg::FlagSubmittedClick => {
+            let flag = model.input_text_flag.trim().to_string();
+            if flag.is_empty() {
+                return None;
+            }
+            model.input_text_flag.clear();
+            return Some(Action::SubmitFlag { challenge_id: model.challenge_id.clone(), flag });
+        }
+        Msg::HintUnlockClick(hint_id) => {
+            model.hint_to_confirm = Some(hint_id);
+        }
+        Msg::HintUnlockCancelled => {
+            model.hint_to_confirm = None;
+        }
+        Msg::HintUnlockConfirmed => {
+            return model.hint_to_confirm.take().map(Action::UnlockHint);
+        }
+        Msg::AttachmentDownloadClick(attachment_id) => {
+            return Some(Action::DownloadAttachment(attachment_id));
+        }
+    }
+    None
+}
+
+// ------ ------
+//     View
+// ------ ------
+
+pub fn view(model: &Model, app: &crate::Model) -> Node<Msg> {
+    let urls = || Urls::new(&app.base_url);
+    let challenge = app.challenges.iter()
+        .find(|challenge| challenge.id == model.challenge_id)
+        .filter(|_| app.phase.map_or(false, EventPhase::challenges_visible));
+    let challenge = match challenge {
+        Some(challenge) => challenge,
+        None => {
+            return div![
+                a![attrs! { At::Href => urls().challenges() }, "All challenges"],
+                p![C!["mt-3"], "This challenge does not exist or is not released yet."],
+                style! {
+                    St::Color => "#FFFFFF",
+                }
+            ];
+        }
+    };
+    // Earliest first, the first one is the first blood
+    let solves = standings_solves(app).iter()
+        .filter(|solve| solve.challenge_id == challenge.id)
+        .sorted_by(|a, b| a.solved_at.partial_cmp(&b.solved_at).unwrap())
+        .collect::<Vec<_>>();
+
+    div![
+        a![attrs! { At::Href => urls().challenges() }, "All challenges"],
+        p![C!["mt-3"],
+            challenge.author.as_ref().map(|author| span![C!["mr-sm-3"], format!("By {}", author)]),
+            span![C!["mr-sm-3"], format!("{} of {} points left", challenge.value, challenge.points)],
+            span![match solves.len() {
+                1 => "1 solve".to_string(),
+                count => format!("{} solves", count),
+            }],
+        ],
+        IF!(!app.phase.map_or(false, EventPhase::submissions_open) => div![C!["alert alert-secondary"], "Submissions are closed."]),
+        view_team_select(app, Msg::PlayerTeamChanged),
+        view_card(model, app, challenge),
+        //
+        // Solving teams
+        //
+        h4!["Solved by"],
+        if solves.is_empty() {
+            p!["Nobody yet."]
+        } else {
+            table![C!["table table-sm table-dark"],
+                thead![
+                    tr![
+                        th![ C!["text-center"], attrs! { At::Scope => "col", }, "#" ],
+                        th![ attrs! { At::Scope => "col", }, "Team" ],
+                        th![ attrs! { At::Scope => "col", }, "Solved at" ],
+                        th![ C!["text-right"], attrs! { At::Scope => "col", }, "Time" ],
+                    ]
+                ],
+                tbody![
+                    solves.iter().enumerate().map(|(index, solve)| tr![
+                        th![ C!["text-center"], attrs! { At::Scope => "row", }, (index + 1).to_string() ],
+                        td![ a![attrs! { At::Href => urls().team(&solve.team_id) }, team_name(app, &solve.team_id)] ],
+                        td![ format_time(app, solve.solved_at) ],
+                        // Since the start of the event
+                        td![ C!["text-right"],
+                            match &app.event {
+                                Some(event) => Clock::from_ms((solve.solved_at - event.starts_at) as i64).get_time(),
+                                None => "-".to_string(),
+                            }
+                        ],
+                    ]),
+                ],
+            ]
+        },
+        style! {
+            St::Color => "#FFFFFF",
+        }
+    ]
+}
+
+///
+/// Description, flag form, attachments, hints and submissions of the challenge.
+///
+/// * `model` - Model of the challenge.
+/// * `app` - Model of the app.
+/// * `challenge` - The challenge of `model`.
+pub fn view_card(model: &Model, app: &crate::Model, challenge: &Challenge) -> Node<Msg> {
+    if let Some(releases_at) = challenge.releases_at.filter(|_| !challenge.is_released(app.now)) {
+        return view_unreleased_challenge(app, releases_at);
+    }
+    let missing = challenge_graph::missing_prerequisites(challenge, |challenge_id| {
+        match &app.player_team_id {
+            Some(team_id) => is_solved(app, team_id, challenge_id),
+            None => false,
+        }
+    });
+    if !missing.is_empty() {
+        return view_locked_challenge(app, challenge, &missing);
+    }
+    div![C!["card bg-dark mb-3"],
+        div![C!["card-body"],
+            h5![C!["card-title"],
+                format!("{} - {}", challenge.letter, challenge.name),
+                style! {
+                    St::Color => "#FFFFFF",
+                }
+            ],
+            h6![C!["card-subtitle mb-2"],
+                format!("{} | {} points", challenge.category, challenge.value),
+                style! {
+                    St::Color => "#9580ff",
+                }
+            ],
+            div![
+                // The class required by GitHub styles. See `index.html`.
+                C!["markdown-body"],
+                style![
+                    St::Color => "#FFFFFF",
+                ],
+                markdown::view(&challenge.description),
+            ],
+            form![C!["form-inline"],
+                input![C!["form-control mr-sm-2"],
+                    attrs! {
+                        At::Type => "text",
+                        At::Value => model.input_text_flag,
+                        At::Placeholder => "flag",
+                    },
+                    input_ev(Ev::Input, Msg::InputTextFlagChanged),
+                ],
+                button![C!["btn"], "Submit",
+                    attrs! {
+                        At::Type => "submit",
+                        At::Disabled => (
+                            app.player_team_id.is_none()
+                                || model.input_text_flag.trim().is_empty()
+                                || !app.phase.map_or(false, EventPhase::submissions_open)
+                        ).as_at_value(),
+                    },
+                    style! {
+                        St::BackgroundColor => "#50fa7b",
+                    }
+                ],
+                ev(Ev::Submit, |event| {
+                    event.prevent_default();
+                    Msg::FlagSubmittedClick
+                }),
+            ],
+            //
+            // Attachments
+            //
+            IF!(!challenge.attachments.is_empty() => ul![C!["list-unstyled"],
+                challenge.attachments.iter().map(|attachment| view_attachment(app, attachment)),
+            ]),
+            //
+            // Hints
+            //
+            app.hints.iter()
+                .filter(|hint| hint.challenge_id == challenge.id)
+                .enumerate()
+                .map(|(index, hint)| view_hint(app, index, hint)),
+            view_hint_confirmation(model, app),
+            //
+            // Submission history, newest first
+            //
+            ul![C!["list-unstyled mt-2 mb-0"],
+                app.submissions.iter().rev()
+                    .filter(|submission| submission.challenge_id == challenge.id)
+                    .map(|submission| view_submission(app, submission)),
+            ],
+        ],
+    ]
+}
+
+//
+// Cost of the hint the player clicked, only while hints can be unlocked
+//
+fn view_hint_confirmation(model: &Model, app: &crate::Model) -> Node<Msg> {
+    if !app.phase.map_or(false, EventPhase::submissions_open) {
+        return empty![];
+    }
+    let hint = match model.hint_to_confirm.as_ref()
+        .and_then(|hint_id| app.hints.iter().find(|hint| &hint.id == hint_id)) {
+        Some(hint) => hint,
+        None => return empty![],
+    };
+    div![C!["alert alert-warning mt-2"],
+        attrs! { At::Custom("role".into()) => "alertdialog" },
+        p![format!("Unlocking this hint costs {} points. This cannot be undone.", hint.cost)],
+        button![C!["btn btn-warning mr-sm-2"], "Unlock",
+            attrs! { At::Type => "button" },
+            ev(Ev::Click, |_| Msg::HintUnlockConfirmed),
+        ],
+        button![C!["btn btn-secondary"], "Cancel",
+            attrs! { At::Type => "button" },
+            ev(Ev::Click, |_| Msg::HintUnlockCancelled),
+        ],
+    ]
+}
+
+fn view_unreleased_challenge(app: &crate::Model, releases_at: f64) -> Node<Msg> {
+    let countdown = Clock::from_ms((releases_at - app.now) as i64);
+    div![C!["card bg-dark mb-3"],
+        div![C!["card-body"],
+            h5![C!["card-title"],
+                format!("New challenge in {}", countdown.get_time()),
+                attrs! { At::Title => countdown.get_time_verbose() },
+            ],
+            h6![C!["card-subtitle"],
+                format!("Released {}", format_time(app, releases_at)),
+            ],
+        ],
+        style! {
+            St::Color => "#8be9fd",
+            St::BorderStyle => "dashed",
+            St::BorderColor => "#8be9fd",
+        }
+    ]
+}
+
+fn view_locked_challenge(app: &crate::Model, challenge: &Challenge, missing: &[&Id]) -> Node<Msg> {
+    let names = missing.iter()
+        .map(|id| match app.challenges.iter().find(|c| &&c.id == id) {
+            Some(prerequisite) => format!("{} - {}", prerequisite.letter, prerequisite.name),
+            None => id.to_string(),
+        })
+        .join(", ");
+    div![C!["card bg-dark mb-3"],
+        div![C!["card-body"],
+            h5![C!["card-title"],
+                format!("{} - {} (locked)", challenge.letter, challenge.name),
+            ],
+            h6![C!["card-subtitle"],
+                format!("Solve {} to unlock this challenge", names),
+            ],
+        ],
+        style! {
+            St::Color => "#6272a4",
+            St::Opacity => "0.6",
+        }
+    ]
+}
+
+fn view_attachment(app: &crate::Model, attachment: &Attachment) -> Node<Msg> {
+    li![C!["mb-2"],
+        button![C!["btn btn-sm btn-outline-light mr-sm-2"],
+            format!("{} ({})", attachment.name, format_size(attachment.size)),
+            attrs! {
+                At::Type => "button",
+                At::Disabled => app.player_team_id.is_none().as_at_value(),
+            },
+            {
+                let id = attachment.id.clone();
+                ev(Ev::Click, move |_| Msg::AttachmentDownloadClick(id))
+            },
+        ],
+        br![],
+        small![
+            "SHA-256: ",
+            code![&attachment.sha256,
+                attrs! { At::Title => format!("sha256sum {}", attachment.name) },
+                style! {
+                    St::WordBreak => "break-all",
+                }
+            ],
+        ],
+        style! {
+            St::Color => "#FFFFFF",
+        }
+    ]
+}
+
+//
+// Human readable file size, e.g. 1.5 MiB
+//
+fn format_size(bytes: i64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[unit])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+fn view_hint(app: &crate::Model, index: usize, hint: &Hint) -> Node<Msg> {
+    match &hint.content {
+        Some(content) => div![
+            // The class required by GitHub styles. See `index.html`.
+            C!["markdown-body mt-2"],
+            style![
+                St::Color => "#f1fa8c",
+            ],
+            strong![format!("Hint {}", index + 1)],
+            markdown::view(content),
+        ],
+        None => button![C!["btn btn-sm btn-outline-warning mt-2 mr-sm-2"],
+            format!("Hint {} ({} points)", index + 1, hint.cost),
+            attrs! {
+                At::Type => "button",
+                At::Disabled => (!app.phase.map_or(false, EventPhase::submissions_open)).as_at_value(),
+            },
+            {
+                let id = hint.id.clone();
+                ev(Ev::Click, move |_| Msg::HintUnlockClick(id))
+            },
+        ],
+    }
+}
+
+fn view_submission(app: &crate::Model, submission: &Submission) -> Node<Msg> {
+    let (color, text) = match &submission.outcome {
+        None => ("#FFFFFF", "checking...".to_string()),
+        Some(SubmissionOutcome::Correct) => ("#50fa7b", "correct".to_string()),
+        Some(SubmissionOutcome::Incorrect) => ("#ff5555", "incorrect".to_string()),
+        Some(SubmissionOutcome::AlreadySolved) => ("#f1fa8c", "already solved".to_string()),
+        Some(SubmissionOutcome::RateLimited(Some(seconds))) => ("#ffb86c", format!("rate limited, retry in {}s", seconds)),
+        Some(SubmissionOutcome::RateLimited(None)) => ("#ffb86c", "rate limited".to_string()),
+        Some(SubmissionOutcome::Failed(reason)) => ("#ff5555", format!("failed: {}", reason)),
+    };
+    li![
+        format!("{} ", format_time_of_day(app, submission.submitted_at)),
+        code![&submission.flag],
+        span![format!(" {}", text),
+            style! {
+                St::Color => color,
+            }
+        ],
+        style! {
+            St::Color => "#FFFFFF",
+        }
+    ]
+}
@@ -0,0 +1,209 @@
+use super::{challenge, view_team_select, Action};
+use crate::{chart, shared::Clock, solve_stats, standings_solves, view_export_button, ChartKind, EventPhase, Id};
+use seed::{prelude::*, *};
+use std::collections::HashMap;
+
+// Bars of the time to solve histograms
+const STATS_BINS: usize = 12;
+
+// ------ ------
+//     Model
+// ------ ------
+
+pub struct Model {
+    // Inputs per challenge id, added once the player interacts with a card
+    cards: HashMap<Id, challenge::Model>,
+}
+
+pub fn init() -> Model {
+    Model {
+        cards: HashMap::new(),
+    }
+}
+
+///
+/// Forget the inputs of the previous player.
+///
+pub fn player_changed(model: &mut Model) {
+    model.cards.clear();
+}
+
+// ------ ------
+//    Update
+// ------ ------
+
+pub enum Msg {
+    PlayerTeamChanged(Id),
+    Challenge(Id, challenge::Msg),
+    ChartExportClick(ChartKind),
+}
+
+///
+/// Returns what the app has to carry out, e.g. submit a flag.
+///
+/// * `msg` - Message of the page.
+/// * `model` - Model of the page.
+pub fn update(msg: Msg, model: &mut Model) -> Option<Action> {
+    match msg {
+        Msg::PlayerTeamChanged(team_id) => {
+            // Hints to confirm belong to the previous team
+            for card in model.cards.values_mut() {
+                challenge::update(challenge::Msg::HintUnlockCancelled, card);
+            }
+            Some(Action::SelectTeam(team_id))
+        }
+        Msg::Challenge(challenge_id, msg) => {
+            let card = model.cards.entry(challenge_id.clone()).or_insert_with(|| challenge::init(challenge_id));
+            challenge::update(msg, card)
+        }
+        Msg::ChartExportClick(kind) => Some(Action::ExportChart(kind)),
+    }
+}
+
+// ------ ------
+//     View
+// ------ ------
+
+pub fn view(model: &Model, app: &crate::Model) -> Node<Msg> {
+    div![
+        view_challenges(model, app),
+        view_solve_stats(app),
+    ]
+}
+
+fn view_challenges(model: &Model, app: &crate::Model) -> Node<Msg> {
+    if !app.phase.map_or(false, EventPhase::challenges_visible) {
+        return div![
+            h3![C!["description"], "Challenges",
+                style!{
+                    St::Color => "#50fa7b"
+                },
+            ],
+            p!["Challenges are revealed when the event starts.",
+                style! {
+                    St::Color => "#FFFFFF",
+                }
+            ],
+        ];
+    }
+    let submissions_open = app.phase.map_or(false, EventPhase::submissions_open);
+    div![
+        h3![C!["description"], "Challenges",
+            style!{
+                St::Color => "#50fa7b"
+            },
+        ],
+        IF!(!submissions_open => div![C!["alert alert-secondary"], "Submissions are closed."]),
+        view_team_select(app, Msg::PlayerTeamChanged),
+        app.challenges.iter().map(|challenge| {
+            let id = challenge.id.clone();
+            let card = match model.cards.get(&challenge.id) {
+                Some(card) => challenge::view_card(card, app, challenge),
+                None => challenge::view_card(&challenge::init(challenge.id.clone()), app, challenge),
+            };
+            card.map_msg(move |msg| Msg::Challenge(id.clone(), msg))
+        }),
+    ]
+}
+
+//
+// Which challenges are hard, from the solves received so far
+//
+fn view_solve_stats(app: &crate::Model) -> Node<Msg> {
+    if !app.phase.map_or(false, EventPhase::challenges_visible) {
+        return empty![];
+    }
+    let stats = solve_stats::compute(
+        &app.challenges,
+        standings_solves(app),
+        app.event.as_ref().map(|event| event.starts_at),
+        STATS_BINS,
+    );
+    let max_count = stats.challenges.iter().flat_map(|c| c.histogram.iter()).copied().max().unwrap_or(0);
+    let bin_label = stats_bin_label(stats.bin_width);
+    div![
+        h3![C!["description"], "Statistics",
+            view_export_button(ChartKind::SolveStats, solve_stats_plottable(app), Msg::ChartExportClick),
+            style!{
+                St::Color => "#50fa7b"
+            },
+        ],
+        p![format!("{} active teams", stats.active_teams),
+            style! {
+                St::Color => "#FFFFFF",
+            }
+        ],
+        table![C!["table table-sm table-dark"],
+            thead![
+                tr![
+                    th![ attrs! { At::Scope => "col", }, "Challenge" ],
+                    th![ C!["text-right"], attrs! { At::Scope => "col", }, "Solves" ],
+                    th![ C!["text-right"], attrs! { At::Scope => "col", }, "Solve rate" ],
+                    th![ C!["text-right"], attrs! { At::Scope => "col", }, "Median time to solve" ],
+                    th![ attrs! { At::Scope => "col", At::Title => "Solves by time since release" }, "Time to solve" ],
+                ]
+            ],
+            tbody![
+                stats.challenges.iter().map(|challenge_stats| tr![
+                    td![ format!("{} - {}", challenge_stats.challenge.letter, challenge_stats.challenge.name) ],
+                    td![ C!["text-right"], challenge_stats.solves.to_string() ],
+                    td![ C!["text-right"], format!("{:.0}%", challenge_stats.solve_rate * 100.0) ],
+                    td![ C!["text-right"], match challenge_stats.median_time_to_solve {
+                        Some(median) => Clock::from_ms(median as i64).get_time(),
+                        None => "-".to_string(),
+                    }],
+                    td![ chart::histogram(&challenge_stats.histogram, max_count, chart::PALETTE[1], &bin_label) ],
+                ]),
+            ],
+        ],
+    ]
+}
+
+///
+/// Whether a histogram has a bar, the export is disabled otherwise.
+///
+/// * `app` - Model of the app.
+pub fn solve_stats_plottable(app: &crate::Model) -> bool {
+    !app.challenges.is_empty() && !standings_solves(app).is_empty()
+}
+
+///
+/// Time to solve histograms of all challenges on one chart, for the SVG export.
+///
+/// * `app` - Model of the app.
+pub fn solve_stats_chart<Ms>(app: &crate::Model) -> Node<Ms> {
+    let stats = solve_stats::compute(
+        &app.challenges,
+        standings_solves(app),
+        app.event.as_ref().map(|event| event.starts_at),
+        STATS_BINS,
+    );
+    let rows: Vec<(String, Vec<usize>)> = stats.challenges.iter().map(|challenge_stats| (
+        format!(
+            "{} - {} ({}, {:.0}%)",
+            challenge_stats.challenge.letter,
+            challenge_stats.challenge.name,
+            challenge_stats.solves,
+            challenge_stats.solve_rate * 100.0
+        ),
+        challenge_stats.histogram.clone(),
+    )).collect();
+    chart::histogram_rows(
+        "Solves by time since release",
+        &format!(
+            "{} active teams, {} per bar",
+            stats.active_teams,
+            Clock::from_ms(stats.bin_width as i64).get_time()
+        ),
+        &rows,
+        &stats_bin_label(stats.bin_width),
+    )
+}
+
+fn stats_bin_label(bin_width: f64) -> impl Fn(usize) -> String {
+    move |index| format!(
+        "{} - {}",
+        Clock::from_ms((index as f64 * bin_width) as i64).get_time(),
+        Clock::from_ms(((index + 1) as f64 * bin_width) as i64).get_time(),
+    )
+}
@@ -0,0 +1,313 @@
+use crate::{m_log_in, m_sign_up, send_graphql_request, GQLResponse, GraphQLQuery, MLogIn, MSignUp, Urls};
+use crate::session::Session;
+use seed::{prelude::*, *};
+
+const MIN_PASSWORD_LENGTH: usize = 8;
+
+// ------ ------
+//     Model
+// ------ ------
+
+pub struct Model {
+    // Sign up form instead of the log in form
+    sign_up: bool,
+    input_text_name: String,
+    input_text_email: String,
+    input_text_password: String,
+    // Waiting for the server, the form cannot be sent twice
+    submitting: bool,
+    errors: Vec<FieldError>,
+}
+
+pub fn init(sign_up: bool) -> Model {
+    Model {
+        sign_up,
+        input_text_name: String::new(),
+        input_text_email: String::new(),
+        input_text_password: String::new(),
+        submitting: false,
+        errors: Vec::new(),
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Field {
+    Name,
+    Email,
+    Password,
+    // Errors not tied to an input, e.g. wrong credentials
+    Form,
+}
+
+impl Field {
+    fn from_key(key: Option<&str>) -> Field {
+        match key {
+            Some("name") => Field::Name,
+            Some("email") => Field::Email,
+            Some("password") => Field::Password,
+            _ => Field::Form,
+        }
+    }
+}
+
+struct FieldError {
+    field: Field,
+    message: String,
+}
+
+impl FieldError {
+    fn new(field: Field, message: impl Into<String>) -> FieldError {
+        FieldError { field, message: message.into() }
+    }
+}
+
+//
+// Errors the server would report anyway, caught before sending the form
+//
+fn validate(model: &Model) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    if model.input_text_name.trim().is_empty() {
+        errors.push(FieldError::new(Field::Name, "Enter your name."));
+    }
+    if model.sign_up {
+        let email = model.input_text_email.trim();
+        if email.is_empty() {
+            errors.push(FieldError::new(Field::Email, "Enter your email."));
+        } else if !email.contains('@') {
+            errors.push(FieldError::new(Field::Email, "This is not an email address."));
+        }
+        if model.input_text_password.chars().count() < MIN_PASSWORD_LENGTH {
+            errors.push(FieldError::new(
+                Field::Password,
+                format!("Use at least {} characters.", MIN_PASSWORD_LENGTH),
+            ));
+        }
+    } else if model.input_text_password.is_empty() {
+        errors.push(FieldError::new(Field::Password, "Enter your password."));
+    }
+    errors
+}
+
+// ------ ------
+//    Update
+// ------ ------
+
+pub enum Msg {
+    InputTextNameChanged(String),
+    InputTextEmailChanged(String),
+    InputTextPasswordChanged(String),
+    SubmitClick,
+    SignedUp(fetch::Result<GQLResponse<m_sign_up::ResponseData>>),
+    LoggedIn(fetch::Result<GQLResponse<m_log_in::ResponseData>>),
+}
+
+///
+/// Returns the new session once the server accepted the form.
+///
+/// * `msg` - Message of the page.
+/// * `model` - Model of the page.
+/// * `orders` - Orders of the page.
+pub fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) -> Option<Session> {
+    match msg {
+        Msg::InputTextNameChanged(input_text) => {
+            model.input_text_name = input_text;
+        }
+        Msg::InputTextEmailChanged(input_text) => {
+            model.input_text_email = input_text;
+        }
+        Msg::InputTextPasswordChanged(input_text) => {
+            model.input_text_password = input_text;
+        }
+        Msg::SubmitClick => {
+            if model.submitting {
+                return None;
+            }
+            model.errors = validate(model);
+            if !model.errors.is_empty() {
+                return None;
+            }
+            model.submitting = true;
+            let name = model.input_text_name.trim().to_string();
+            let password = model.input_text_password.clone();
+            if model.sign_up {
+                let variables = m_sign_up::Variables {
+                    name,
+                    email: model.input_text_email.trim().to_string(),
+                    password,
+                };
+                orders.perform_cmd(async {
+                    Msg::SignedUp(send_graphql_request(&MSignUp::build_query(variables)).await)
+                });
+            } else {
+                let variables = m_log_in::Variables { name, password };
+                orders.perform_cmd(async {
+                    Msg::LoggedIn(send_graphql_request(&MLogIn::build_query(variables)).await)
+                });
+            }
+        }
+        Msg::SignedUp(Ok(GQLResponse {
+            data: Some(data), ..
+        })) => {
+            let result = data.sign_up;
+            return accepted(
+                model,
+                result.token,
+                result.user.map(|user| (user.id, user.name)),
+                result.errors.into_iter().map(|error| (error.field, error.message)).collect(),
+            );
+        }
+        Msg::LoggedIn(Ok(GQLResponse {
+            data: Some(data), ..
+        })) => {
+            let result = data.log_in;
+            return accepted(
+                model,
+                result.token,
+                result.user.map(|user| (user.id, user.name)),
+                result.errors.into_iter().map(|error| (error.field, error.message)).collect(),
+            );
+        }
+        Msg::SignedUp(error) => {
+            log!(error);
+            rejected(model);
+        }
+        Msg::LoggedIn(error) => {
+            log!(error);
+            rejected(model);
+        }
+    }
+    None
+}
+
+//
+// Session from the response, the reported errors otherwise
+//
+fn accepted(
+    model: &mut Model,
+    token: Option<String>,
+    user: Option<(String, String)>,
+    errors: Vec<(Option<String>, String)>,
+) -> Option<Session> {
+    model.submitting = false;
+    model.errors = errors
+        .into_iter()
+        .map(|(field, message)| FieldError::new(Field::from_key(field.as_deref()), message))
+        .collect();
+    match (token, user) {
+        (Some(token), Some((user_id, name))) if model.errors.is_empty() => {
+            model.input_text_password.clear();
+            Some(Session { user_id, name, token })
+        }
+        _ => {
+            if model.errors.is_empty() {
+                rejected(model);
+            }
+            None
+        }
+    }
+}
+
+//
+// The request failed without saying which input is wrong
+//
+fn rejected(model: &mut Model) {
+    model.submitting = false;
+    model.errors = vec![FieldError::new(Field::Form, "Something went wrong, please try again.")];
+}
+
+// ------ ------
+//     View
+// ------ ------
+
+pub fn view(model: &Model, base_url: &Url) -> Node<Msg> {
+    div![
+        h3![C!["description"], if model.sign_up { "Sign Up" } else { "Log in" },
+            style!{
+                St::Color => "#50fa7b"
+            },
+        ],
+        view_errors(model, Field::Form).map(|messages| div![C!["alert alert-danger"], messages]),
+        form![
+            div![C!["form-group"],
+                label!["name"],
+                input![C!["form-control", IF!(has_errors(model, Field::Name) => "is-invalid")],
+                    attrs! {
+                        At::Type => "text",
+                        At::Value => model.input_text_name,
+                        At::Placeholder => "name",
+                        At::AutoComplete => "username",
+                    },
+                    input_ev(Ev::Input, Msg::InputTextNameChanged),
+                ],
+                view_errors(model, Field::Name).map(|messages| div![C!["invalid-feedback"], messages]),
+            ],
+            IF!(model.sign_up => div![C!["form-group"],
+                label!["email"],
+                input![C!["form-control", IF!(has_errors(model, Field::Email) => "is-invalid")],
+                    attrs! {
+                        At::Type => "email",
+                        At::Value => model.input_text_email,
+                        At::Placeholder => "email",
+                        At::AutoComplete => "email",
+                    },
+                    input_ev(Ev::Input, Msg::InputTextEmailChanged),
+                ],
+                view_errors(model, Field::Email).map(|messages| div![C!["invalid-feedback"], messages]),
+            ]),
+            div![C!["form-group"],
+                label!["password"],
+                input![C!["form-control", IF!(has_errors(model, Field::Password) => "is-invalid")],
+                    attrs! {
+                        At::Type => "password",
+                        At::Value => model.input_text_password,
+                        At::Placeholder => "password",
+                        At::AutoComplete => if model.sign_up { "new-password" } else { "current-password" },
+                    },
+                    input_ev(Ev::Input, Msg::InputTextPasswordChanged),
+                ],
+                view_errors(model, Field::Password).map(|messages| div![C!["invalid-feedback"], messages]),
+            ],
+            button![C!["btn mb-3"], if model.sign_up { "Sign Up" } else { "Log in" },
+                attrs! {
+                    At::Type => "submit",
+                    At::Disabled => model.submitting.as_at_value(),
+                },
+                style! {
+                    St::BackgroundColor => "#50fa7b",
+                }
+            ],
+            ev(Ev::Submit, |event| {
+                event.prevent_default();
+                Msg::SubmitClick
+            }),
+            style![
+                St::Color => "#9580ff",
+            ],
+        ],
+        if model.sign_up {
+            p!["Already registered? ", a![attrs! { At::Href => Urls::new(base_url).login() }, "Log in"]]
+        } else {
+            p!["No account yet? ", a![attrs! { At::Href => Urls::new(base_url).sign_up() }, "Sign Up"]]
+        },
+        style! {
+            St::Color => "#FFFFFF",
+        }
+    ]
+}
+
+fn has_errors(model: &Model, field: Field) -> bool {
+    model.errors.iter().any(|error| error.field == field)
+}
+
+//
+// Messages of a field, one per line, None without errors
+//
+fn view_errors(model: &Model, field: Field) -> Option<Vec<Node<Msg>>> {
+    if !has_errors(model, field) {
+        return None;
+    }
+    Some(model.errors.iter()
+        .filter(|error| error.field == field)
+        .map(|error| div![&error.message])
+        .collect())
+}
@@ -0,0 +1,50 @@
+use crate::{ChartKind, Id};
+use seed::{prelude::*, *};
+
+pub mod admin;
+pub mod challenge;
+pub mod challenges;
+pub mod login;
+pub mod profile;
+pub mod scoreboard;
+pub mod team;
+
+///
+/// Change of the state shared by all pages, returned by a page `update`.
+///
+/// Pages own their inputs, the app owns the teams, challenges and the
+/// player's submissions and carries these out.
+pub enum Action {
+    SelectTeam(Id),
+    SubmitFlag { challenge_id: Id, flag: String },
+    UnlockHint(Id),
+    DownloadAttachment(Id),
+    UpdateTeam { id: Id, name: String, author: String, points: String },
+    DeleteTeam(Id),
+    ExportChart(ChartKind),
+}
+
+///
+/// Team the flags are submitted for.
+///
+/// * `app` - Model of the app.
+/// * `on_change` - Message with the id of the selected team, empty for none.
+pub fn view_team_select<Ms: 'static>(app: &crate::Model, on_change: fn(Id) -> Ms) -> Node<Ms> {
+    div![C!["form-group"],
+        label!["team"],
+        style![
+            St::Color => "#9580ff",
+        ],
+        select![C!["form-control"],
+            option![attrs! { At::Value => "" }, "-- select your team --"],
+            app.messages.iter().map(|message| {
+                option![
+                    attrs! { At::Value => message.id },
+                    IF!(app.player_team_id.as_ref() == Some(&message.id) => attrs! { At::Selected => AtValue::None }),
+                    &message.name
+                ]
+            }),
+            input_ev(Ev::Change, on_change),
+        ],
+    ]
+}
@@ -0,0 +1,75 @@
+use super::{view_team_select, Action};
+use crate::{format_time_of_day, Id, LedgerEntry};
+use seed::{prelude::*, *};
+
+// ------ ------
+//    Update
+// ------ ------
+
+pub enum Msg {
+    PlayerTeamChanged(Id),
+}
+
+///
+/// Returns what the app has to carry out, the page has no state of its own.
+///
+/// * `msg` - Message of the page.
+pub fn update(msg: Msg) -> Option<Action> {
+    match msg {
+        Msg::PlayerTeamChanged(team_id) => Some(Action::SelectTeam(team_id)),
+    }
+}
+
+// ------ ------
+//     View
+// ------ ------
+
+//
+// Settings of the player
+//
+pub fn view(app: &crate::Model) -> Node<Msg> {
+    div![
+        h3![C!["description"], "Profile",
+            style!{
+                St::Color => "#50fa7b"
+            },
+        ],
+        view_team_select(app, Msg::PlayerTeamChanged),
+        view_ledger(app),
+    ]
+}
+
+fn view_ledger(app: &crate::Model) -> Node<Msg> {
+    let team_id = match &app.player_team_id {
+        Some(team_id) => team_id,
+        None => return empty![],
+    };
+    let entries: Vec<&LedgerEntry> = app.ledger.iter()
+        .filter(|entry| &entry.team_id == team_id)
+        .collect();
+    if entries.is_empty() {
+        return empty![];
+    }
+    table![C!["table table-sm table-dark"],
+        thead![
+            tr![
+                th![ attrs! { At::Scope => "col", }, "Time" ],
+                th![ attrs! { At::Scope => "col", }, "Score ledger" ],
+                th![ C!["text-right"], attrs! { At::Scope => "col", }, "Points" ],
+            ]
+        ],
+        tbody![
+            entries.iter().rev().map(|entry| {
+                tr![
+                    td![ format_time_of_day(app, entry.at) ],
+                    td![ &entry.description ],
+                    td![ C!["text-right"], format!("{:+}", entry.points),
+                        style! {
+                            St::Color => if entry.points < 0 { "#ff5555" } else { "#50fa7b" },
+                        }
+                    ],
+                ]
+            }),
+        ],
+    ]
+}
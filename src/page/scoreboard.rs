@@ -0,0 +1,570 @@
+use super::Action;
+use crate::{
+    activity, chart, format_time, is_solved, q_attempts, score_chart, score_graph_plottable,
+    send_graphql_request, shared::Clock, standings_solves, standings_teams, team_name, text_chart, view_export_button,
+    ActivityKind, Challenge, ChartKind, EventPhase, GQLResponse, GraphQLQuery, Id, Message, QAttempts, Urls,
+};
+use itertools::Itertools;
+use seed::{prelude::*, *};
+
+// Columns of the text score graph
+const GRAPH_TEXT_WIDTH: usize = 100;
+const GRAPH_DEFAULT_TOP_TEAMS: usize = 5;
+
+// ------ ------
+//     Model
+// ------ ------
+
+pub struct Model {
+    // Number of leading teams plotted when none are selected
+    graph_top_teams: usize,
+    graph_selected_teams: Vec<Id>,
+    // Show the ASCII plots instead of the SVG chart
+    graph_as_text: bool,
+    heatmap_mode: HeatmapMode,
+    // Only fetched while the heatmap shows attempts
+    attempts: Vec<AttemptCount>,
+    // Kinds filtered out of the activity feed
+    activity_hidden_kinds: Vec<ActivityKind>,
+}
+
+pub fn init() -> Model {
+    Model {
+        graph_top_teams: GRAPH_DEFAULT_TOP_TEAMS,
+        graph_selected_teams: vec![],
+        graph_as_text: false,
+        heatmap_mode: HeatmapMode::SolveTime,
+        attempts: vec![],
+        activity_hidden_kinds: vec![],
+    }
+}
+
+///
+/// Stop plotting a team removed from the scoreboard.
+///
+/// * `team_id` - The deleted team.
+pub fn team_deleted(model: &mut Model, team_id: &str) {
+    model.graph_selected_teams.retain(|id| id != team_id);
+}
+
+#[derive(Clone, Debug)]
+struct AttemptCount {
+    team_id: Id,
+    challenge_id: Id,
+    count: i64,
+}
+
+// What the color of a heatmap cell encodes
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum HeatmapMode {
+    SolveTime,
+    Attempts,
+}
+
+impl HeatmapMode {
+    fn key(self) -> &'static str {
+        match self {
+            HeatmapMode::SolveTime => "solve-time",
+            HeatmapMode::Attempts => "attempts",
+        }
+    }
+}
+
+// ------ ------
+//    Update
+// ------ ------
+
+pub enum Msg {
+    GraphTopTeamsChanged(String),
+    GraphTeamToggled(Id),
+    GraphAsTextToggled,
+    HeatmapModeChanged(String),
+    AttemptsFetched(fetch::Result<GQLResponse<q_attempts::ResponseData>>),
+    ActivityFilterToggled(ActivityKind),
+    ChartExportClick(ChartKind),
+    TeamUpdatedClick(Id),
+    TeamDeletedClick(Id),
+}
+
+///
+/// Returns what the app has to carry out, e.g. export a chart.
+///
+/// * `msg` - Message of the page.
+/// * `model` - Model of the page.
+/// * `orders` - Orders of the page.
+pub fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) -> Option<Action> {
+    match msg {
+        Msg::GraphTopTeamsChanged(input_text) => {
+            if let Ok(count) = input_text.parse::<usize>() {
+                model.graph_top_teams = count.max(1);
+            }
+        }
+        Msg::GraphTeamToggled(team_id) => {
+            match model.graph_selected_teams.iter().position(|id| id == &team_id) {
+                Some(index) => { model.graph_selected_teams.remove(index); }
+                None => model.graph_selected_teams.push(team_id),
+            }
+        }
+        Msg::GraphAsTextToggled => {
+            model.graph_as_text = !model.graph_as_text;
+        }
+        Msg::HeatmapModeChanged(key) => {
+            model.heatmap_mode = if key == HeatmapMode::Attempts.key() {
+                orders.perform_cmd(async {
+                    Msg::AttemptsFetched(send_graphql_request(&QAttempts::build_query(q_attempts::Variables)).await)
+                });
+                HeatmapMode::Attempts
+            } else {
+                HeatmapMode::SolveTime
+            };
+        }
+        Msg::AttemptsFetched(Ok(GQLResponse {
+            data: Some(data), ..
+        })) => {
+            model.attempts = data.attempts.into_iter().map(|attempt| AttemptCount {
+                team_id: attempt.team_id,
+                challenge_id: attempt.challenge_id,
+                count: attempt.count,
+            }).collect();
+        }
+        Msg::AttemptsFetched(error) => log!(error),
+        Msg::ActivityFilterToggled(kind) => {
+            match model.activity_hidden_kinds.iter().position(|hidden| *hidden == kind) {
+                Some(index) => { model.activity_hidden_kinds.remove(index); }
+                None => model.activity_hidden_kinds.push(kind),
+            }
+        }
+        Msg::ChartExportClick(kind) => {
+            return Some(Action::ExportChart(kind));
+        }
+        Msg::TeamUpdatedClick(id) => {
+            return Some(Action::UpdateTeam {
+                id,
+                name: "test".to_string(),
+                author: "test".to_string(),
+                points: "100".to_string(),
+            });
+        }
+        Msg::TeamDeletedClick(id) => {
+            return Some(Action::DeleteTeam(id));
+        }
+    }
+    None
+}
+
+// ------ ------
+//     View
+// ------ ------
+
+pub fn view(model: &Model, app: &crate::Model) -> Node<Msg> {
+    div![
+        view_score_graph(model, app),
+        view_heatmap(model, app),
+        //
+        // Scoring
+        //
+        view_standings(app),
+        view_activity(model, app),
+    ]
+}
+
+///
+/// Teams by points, one page of them in kiosk mode.
+///
+/// * `app` - Model of the app.
+pub fn view_standings(app: &crate::Model) -> Node<Msg> {
+    let ranks = match &app.kiosk {
+        Some(kiosk) => kiosk.ranks(standings_teams(app).len()),
+        None => 0..standings_teams(app).len(),
+    };
+    div![
+        IF!(app.phase == Some(EventPhase::Frozen) =>
+            div![C!["alert alert-info"], "The scoreboard is frozen until the end of the event."]
+        ),
+        table![C!["table table-striped table-bordered table-dark"],
+            //
+            // Table headers
+            //
+            thead![
+                tr![
+                    th![ C!["text-center"], attrs! { At::Scope => "col", }, "#" ],
+                    th![ C!["text-center"], attrs! { At::Scope => "col", }, "ID" ],
+                    th![ C!["text-center"], attrs! { At::Scope => "col", }, "Name" ],
+                    th![ C!["text-center"], attrs! { At::Scope => "col", }, "Author" ],
+                    th![ C!["text-center"], attrs! { At::Scope => "col", }, "Points" ],
+                    app.challenges.iter().map(| challenge |
+                        {
+                            match challenge.releases_at {
+                                Some(releases_at) if !challenge.is_released(app.now) => {
+                                    let countdown = Clock::from_ms((releases_at - app.now) as i64);
+                                    th![ C!["text-center"], attrs! { At::Scope => "col", At::Title => format!("Released {}", format_time(app, releases_at)) }, a!["?"],br![],span![countdown.get_time()] ]
+                                }
+                                _ => th![ C!["text-center"], attrs! { At::Scope => "col", At::Title => challenge.name }, a![attrs! { At::Href => Urls::new(&app.base_url).challenge(&challenge.id) }, &challenge.letter],br![],span![challenge.points.to_string()] ],
+                            }
+                        }
+                    ),
+                    IF!(app.kiosk.is_none() => th![ C!["text-center"], attrs! { At::Scope => "col", }, "Actions" ]),
+
+                    // th![ C!["text-center"], attrs! { At::Scope => "col", }, a!["A"],br![],span!["100"] ],
+                    // th![ C!["text-center"], attrs! { At::Scope => "col", }, a!["B"],br![],span!["200"] ],
+                    // th![ C!["text-center"], attrs! { At::Scope => "col", }, a!["C"],br![],span!["300"] ],
+                    // th![ C!["text-center"], attrs! { At::Scope => "col", }, a!["D"],br![],span!["400"] ],
+                    // th![ C!["text-center"], attrs! { At::Scope => "col", }, a!["E"],br![],span!["500"] ],
+                ]
+            ],
+            tbody![
+                //
+                // Sort rows in table in decending order by 'points' key/value
+                // Label new position
+                //
+                // GOTCHA 1: using enumerate() after sorted_by well create
+                // sorted index
+                //
+                // GOTCHA 2: enumerate() creates a tuple (index, &thing)
+                // index = 0, &thing = 1
+                //
+                // Position/Index = some.0
+                // Key/Value = some.1.key
+                //
+                standings_teams(app).iter().sorted_by(|a, b| Ord::cmp(&b.points, &a.points)).enumerate()
+                    .filter(|message| ranks.contains(&message.0))
+                    .map(| message |
+                    {
+                    tr![
+                        td![ C!["text-center"], attrs! { At::Scope => "col", }, format!("{}", message.0+1 ) ],
+                        td![ C!["text-center"], attrs! { At::Scope => "col", }, format!("{}", message.1.id) ],
+                        td![ C!["text-center"], attrs! { At::Scope => "col", },
+                            a![attrs! { At::Href => Urls::new(&app.base_url).team(&message.1.id) }, &message.1.name],
+                        ],
+                        td![ C!["text-center"], attrs! { At::Scope => "col", }, format!("{}", message.1.author) ],
+                        td![ C!["text-center"], attrs! { At::Scope => "col", }, format!("{}", message.1.points) ],
+                        app.challenges.iter().map(|challenge| view_solve_cell(app, &message.1.id, challenge)),
+                        IF!(app.kiosk.is_none() => td![ C!["text-center"], attrs! { At::Scope => "col", },
+                            button![C!["btn"], format!("Update"),
+                                attrs!{ At::Value => &message.1.id },
+                                {
+                                    let id = message.1.id.clone();
+                                    ev(Ev::Click, {
+                                        move |_| Msg::TeamUpdatedClick(id)
+                                    })
+                                },
+                                style! {
+                                    St::BackgroundColor => "#50fa7b",
+                                }
+                            ],
+                            button![C!["btn"], format!("Delete"),
+                                attrs!{ At::Value => &message.1.id },
+                                {
+                                    let id = message.1.id.clone();
+                                    ev(Ev::Click, {
+                                        move |_| Msg::TeamDeletedClick(id)
+                                    })
+                                },
+                                style! {
+                                    St::BackgroundColor => "#50fa7b",
+                                }
+                            ],
+                        ]),
+                        style![
+                            St::Color => "#FFFFFF",
+                        ],
+                    ]
+                    },
+                ),
+            ]
+        ],
+        style! {
+            St::MarginTop => px(15),
+        }
+    ]
+}
+
+//
+// Time from the event start to the team's solve, absolute local time on hover
+//
+fn view_solve_cell(app: &crate::Model, team_id: &str, challenge: &Challenge) -> Node<Msg> {
+    if !challenge.is_released(app.now) {
+        return td![ C!["text-center"], attrs! { At::Scope => "col", } ];
+    }
+    let solve = standings_solves(app).iter()
+        .find(|solve| solve.team_id == team_id && solve.challenge_id == challenge.id);
+    match (solve, &app.event) {
+        (Some(solve), Some(event)) => {
+            let elapsed = Clock::from_ms((solve.solved_at - event.starts_at) as i64);
+            td![ C!["text-center"],
+                attrs! {
+                    At::Scope => "col",
+                    At::Title => format_time(app, solve.solved_at),
+                },
+                a![challenge.points.to_string()],br![],span![elapsed.get_time()]
+            ]
+        }
+        _ => td![ C!["text-center"], attrs! { At::Scope => "col", }, "-" ],
+    }
+}
+
+//
+// Score over time of the plotted teams
+//
+fn view_score_graph(model: &Model, app: &crate::Model) -> Node<Msg> {
+    div![
+        div![C!["form-inline mb-2"],
+            label![C!["mr-sm-2"], "top teams"],
+            input![C!["form-control form-control-sm mr-sm-2"],
+                attrs! {
+                    At::Type => "number",
+                    At::Min => 1,
+                    At::Value => model.graph_top_teams,
+                    At::Disabled => (!model.graph_selected_teams.is_empty()).as_at_value(),
+                },
+                input_ev(Ev::Input, Msg::GraphTopTeamsChanged),
+            ],
+            app.messages.iter().map(|message| {
+                let id = message.id.clone();
+                label![C!["form-check-label mr-sm-2"],
+                    input![C!["form-check-input"],
+                        attrs! {
+                            At::Type => "checkbox",
+                            At::Checked => model.graph_selected_teams.contains(&message.id).as_at_value(),
+                        },
+                        ev(Ev::Change, move |_| Msg::GraphTeamToggled(id)),
+                    ],
+                    &message.name,
+                ]
+            }),
+            button![C!["btn btn-sm btn-outline-secondary"],
+                if model.graph_as_text { "Chart" } else { "Text" },
+                ev(Ev::Click, |_| Msg::GraphAsTextToggled),
+            ],
+            view_export_button(ChartKind::ScoreOverTime, score_graph_plottable(app), Msg::ChartExportClick),
+            style![
+                St::Color => "#9580ff",
+            ],
+        ],
+        if !score_graph_plottable(app) {
+            p!["No scores to plot yet.",
+                style! {
+                    St::Color => "#FFFFFF",
+                }
+            ]
+        } else if model.graph_as_text {
+            view_score_graph_text(model, app)
+        } else {
+            score_graph_chart(model, app)
+        },
+    ]
+}
+
+//
+// Teams shown in the graph, the selection or else the leading teams
+//
+fn graph_teams<'a>(model: &'a Model, app: &'a crate::Model) -> Vec<&'a Id> {
+    if model.graph_selected_teams.is_empty() {
+        app.score_history.top_teams(model.graph_top_teams)
+    } else {
+        model.graph_selected_teams.iter().collect()
+    }
+}
+
+///
+/// Score over time of the plotted teams as an SVG chart, shown and exported.
+///
+/// * `model` - Model of the page.
+/// * `app` - Model of the app.
+pub fn score_graph_chart<Ms: 'static>(model: &Model, app: &crate::Model) -> Node<Ms> {
+    score_chart(app, graph_teams(model, app))
+}
+
+//
+// Fallback, the plotted teams on one text chart
+//
+fn view_score_graph_text(model: &Model, app: &crate::Model) -> Node<Msg> {
+    let series: Vec<text_chart::Series> = graph_teams(model, app)
+        .into_iter()
+        .filter_map(|team_id| {
+            let values = app.score_history.series(team_id).filter(|series| series.len() > 1)?;
+            Some(text_chart::Series {
+                name: team_name(app, team_id),
+                values: values.to_vec(),
+            })
+        })
+        .collect();
+    pre![
+        style![
+            St::Color => "#9580ff",
+        ],
+        text_chart::plot(
+            &series,
+            text_chart::Config::default()
+                .with_offset(10)
+                .with_height(10)
+                .with_width(GRAPH_TEXT_WIDTH)
+                .with_caption("Score over time".to_string()),
+        ),
+    ]
+}
+
+//
+// Teams by challenges, solve time or attempts at a glance
+//
+fn view_heatmap(model: &Model, app: &crate::Model) -> Node<Msg> {
+    if !app.phase.map_or(false, EventPhase::challenges_visible) {
+        return empty![];
+    }
+    div![
+        div![C!["form-inline mb-2"],
+            label![C!["mr-sm-2"], "color by"],
+            select![C!["custom-select custom-select-sm"],
+                [(HeatmapMode::SolveTime, "Solve time"), (HeatmapMode::Attempts, "Attempts")].iter().map(|(mode, label)| {
+                    option![
+                        attrs! { At::Value => mode.key() },
+                        IF!(*mode == model.heatmap_mode => attrs! { At::Selected => AtValue::None }),
+                        label
+                    ]
+                }),
+                input_ev(Ev::Change, Msg::HeatmapModeChanged),
+            ],
+            view_export_button(ChartKind::Heatmap, heatmap_plottable(model, app), Msg::ChartExportClick),
+            style![
+                St::Color => "#9580ff",
+            ],
+        ],
+        heatmap_chart(model, app),
+    ]
+}
+
+///
+/// Whether the heatmap has a cell to color, the export is disabled otherwise.
+///
+/// * `model` - Model of the page.
+/// * `app` - Model of the app.
+pub fn heatmap_plottable(model: &Model, app: &crate::Model) -> bool {
+    let colored = match model.heatmap_mode {
+        HeatmapMode::SolveTime => !standings_solves(app).is_empty(),
+        HeatmapMode::Attempts => model.attempts.iter().any(|attempt| attempt.count > 0),
+    };
+    !standings_teams(app).is_empty() && !app.challenges.is_empty() && colored
+}
+
+///
+/// Teams by challenges in the selected mode, shown and exported.
+///
+/// * `model` - Model of the page.
+/// * `app` - Model of the app.
+pub fn heatmap_chart<Ms>(model: &Model, app: &crate::Model) -> Node<Ms> {
+    let teams: Vec<&Message> = standings_teams(app).iter().sorted_by(|a, b| Ord::cmp(&b.points, &a.points)).collect();
+    let cells = match model.heatmap_mode {
+        HeatmapMode::SolveTime => heatmap_solve_time_cells(app, &teams),
+        HeatmapMode::Attempts => heatmap_attempt_cells(model, app, &teams),
+    };
+    let (low_label, high_label) = match model.heatmap_mode {
+        HeatmapMode::SolveTime => ("early", "late"),
+        HeatmapMode::Attempts => ("first try", "most attempts"),
+    };
+    chart::heatmap(&chart::Heatmap {
+        title: "Teams by challenge".to_string(),
+        rows: teams.iter().map(|team| team.name.clone()).collect(),
+        columns: app.challenges.iter().map(|challenge| challenge.letter.clone()).collect(),
+        cells,
+        low_label: low_label.to_string(),
+        high_label: high_label.to_string(),
+    })
+}
+
+//
+// Solve time from the start of the event, up to the end or the latest solve
+//
+fn heatmap_solve_time_cells(app: &crate::Model, teams: &[&Message]) -> Vec<Vec<Option<chart::HeatmapCell>>> {
+    let first = standings_solves(app).iter().map(|solve| solve.solved_at).fold(f64::INFINITY, f64::min);
+    let last = standings_solves(app).iter().map(|solve| solve.solved_at).fold(f64::NEG_INFINITY, f64::max);
+    let (starts_at, ends_at) = match &app.event {
+        Some(event) => (event.starts_at, event.ends_at.min(last.max(event.starts_at))),
+        None => (first, last),
+    };
+    let duration = (ends_at - starts_at).max(1.0);
+    teams.iter().map(|team| {
+        app.challenges.iter().map(|challenge| {
+            let solve = standings_solves(app).iter()
+                .find(|solve| solve.team_id == team.id && solve.challenge_id == challenge.id)?;
+            let elapsed = Clock::from_ms((solve.solved_at - starts_at).max(0.0) as i64);
+            Some(chart::HeatmapCell {
+                value: (solve.solved_at - starts_at) / duration,
+                label: format!("{}:{:02}", elapsed.days() * 24 + elapsed.hours(), elapsed.minutes()),
+                tooltip: format!("{} solved {} at {}", team.name, challenge.letter, format_time(app, solve.solved_at)),
+            })
+        }).collect()
+    }).collect()
+}
+
+fn heatmap_attempt_cells(model: &Model, app: &crate::Model, teams: &[&Message]) -> Vec<Vec<Option<chart::HeatmapCell>>> {
+    let most = model.attempts.iter().map(|attempt| attempt.count).max().unwrap_or(1);
+    teams.iter().map(|team| {
+        app.challenges.iter().map(|challenge| {
+            let attempt = model.attempts.iter()
+                .find(|attempt| attempt.team_id == team.id && attempt.challenge_id == challenge.id)
+                .filter(|attempt| attempt.count > 0)?;
+            let solved = is_solved(app, &team.id, &challenge.id);
+            Some(chart::HeatmapCell {
+                value: (attempt.count - 1) as f64 / (most - 1).max(1) as f64,
+                label: attempt.count.to_string(),
+                tooltip: format!(
+                    "{}: {} attempts on {}{}",
+                    team.name, attempt.count, challenge.letter, if solved { ", solved" } else { "" }
+                ),
+            })
+        }).collect()
+    }).collect()
+}
+
+//
+// Latest competition events, filterable by kind
+//
+fn view_activity(model: &Model, app: &crate::Model) -> Node<Msg> {
+    div![
+        h3![C!["description"], "Activity",
+            style!{
+                St::Color => "#50fa7b"
+            },
+        ],
+        div![C!["form-inline mb-2"],
+            ActivityKind::ALL.iter().map(|kind| {
+                let kind = *kind;
+                label![C!["form-check-label mr-sm-3"],
+                    input![C!["form-check-input"],
+                        attrs! {
+                            At::Type => "checkbox",
+                            At::Checked => (!model.activity_hidden_kinds.contains(&kind)).as_at_value(),
+                        },
+                        ev(Ev::Change, move |_| Msg::ActivityFilterToggled(kind)),
+                    ],
+                    kind.label(),
+                ]
+            }),
+            style![
+                St::Color => "#9580ff",
+            ],
+        ],
+        ul![C!["list-unstyled"],
+            app.activity.iter()
+                .filter(|activity| !model.activity_hidden_kinds.contains(&activity.kind))
+                .map(|activity| li![
+                    span![C!["mr-sm-2"],
+                        attrs! { At::Title => format_time(app, activity.at) },
+                        activity::format_relative(app.now - activity.at),
+                        style! {
+                            St::Color => "#6272a4",
+                        }
+                    ],
+                    span![&activity.text,
+                        style! {
+                            St::Color => activity.kind.color(),
+                        }
+                    ],
+                ]),
+            style! {
+                St::MaxHeight => px(300),
+                St::OverflowY => "auto",
+            }
+        ],
+    ]
+}
@@ -0,0 +1,172 @@
+use crate::{
+    format_time, q_team, score_chart, score_graph_plottable, send_graphql_request, standings_solves, standings_teams,
+    GQLResponse, GraphQLQuery, Id, QTeam, Urls,
+};
+use itertools::Itertools;
+use seed::{prelude::*, *};
+
+// Submissions listed under recent activity
+const RECENT_SUBMISSIONS: usize = 10;
+
+// ------ ------
+//     Model
+// ------ ------
+
+pub struct Model {
+    team_id: Id,
+    // Members, affiliation and submissions, None until fetched
+    team: Option<q_team::QTeamTeam>,
+    // The team was not found
+    missing: bool,
+}
+
+pub fn init(team_id: Id, orders: &mut impl Orders<Msg>) -> Model {
+    fetch_team(team_id.clone(), orders);
+    Model {
+        team_id,
+        team: None,
+        missing: false,
+    }
+}
+
+fn fetch_team(id: Id, orders: &mut impl Orders<Msg>) {
+    orders.perform_cmd(async {
+        Msg::TeamFetched(send_graphql_request(&QTeam::build_query(q_team::Variables { id })).await)
+    });
+}
+
+///
+/// Refresh the submissions when the subscription reports a solve of the team.
+///
+/// * `team_id` - Team of the solve.
+pub fn solve_received(model: &Model, team_id: &str, orders: &mut impl Orders<Msg>) {
+    if model.team_id == team_id {
+        fetch_team(model.team_id.clone(), orders);
+    }
+}
+
+// ------ ------
+//    Update
+// ------ ------
+
+pub enum Msg {
+    TeamFetched(fetch::Result<GQLResponse<q_team::ResponseData>>),
+}
+
+pub fn update(msg: Msg, model: &mut Model) {
+    match msg {
+        Msg::TeamFetched(Ok(GQLResponse {
+            data: Some(data), ..
+        })) => {
+            model.missing = data.team.is_none();
+            model.team = data.team;
+        }
+        Msg::TeamFetched(error) => log!(error),
+    }
+}
+
+// ------ ------
+//     View
+// ------ ------
+
+pub fn view(model: &Model, app: &crate::Model) -> Node<Msg> {
+    let urls = || Urls::new(&app.base_url);
+    let ranked = standings_teams(app).iter().sorted_by(|a, b| Ord::cmp(&b.points, &a.points)).enumerate();
+    let scoreboard_entry = ranked.into_iter().find(|(_, message)| message.id == model.team_id);
+    let name = match (&scoreboard_entry, &model.team) {
+        (Some((_, team)), _) => team.name.clone(),
+        // Known to the server but not on the scoreboard yet
+        (None, Some(details)) => details.name.clone(),
+        (None, None) => {
+            return div![
+                a![attrs! { At::Href => urls().scoreboard() }, "Scoreboard"],
+                p![C!["mt-3"], if model.missing { "This team does not exist." } else { "Loading..." }],
+                style! {
+                    St::Color => "#FFFFFF",
+                }
+            ];
+        }
+    };
+    let challenge = |challenge_id: &str| app.challenges.iter().find(|challenge| challenge.id == challenge_id);
+    let solves = standings_solves(app).iter()
+        .filter(|solve| solve.team_id == model.team_id)
+        .sorted_by(|a, b| a.solved_at.partial_cmp(&b.solved_at).unwrap());
+
+    div![
+        a![attrs! { At::Href => urls().scoreboard() }, "Scoreboard"],
+        h3![C!["description mt-3"], name,
+            style!{
+                St::Color => "#50fa7b"
+            },
+        ],
+        p![
+            scoreboard_entry.map(|(rank, team)| format!("Rank {} with {} points", rank + 1, team.points)),
+            model.team.as_ref().and_then(|details| details.affiliation.as_ref()).map(|affiliation| {
+                span![C!["ml-sm-2 badge badge-secondary"], affiliation]
+            }),
+        ],
+        //
+        // Members
+        //
+        model.team.as_ref().filter(|details| !details.members.is_empty()).map(|details| p![
+            format!("Members: {}", details.members.iter().map(|member| &member.name).join(", ")),
+        ]),
+        // Rebuilt from the solves, also for a direct link or an ended event
+        if score_graph_plottable(app) {
+            score_chart(app, vec![&model.team_id])
+        } else {
+            p!["No scores to plot yet."]
+        },
+        //
+        // Solves
+        //
+        h4![C!["mt-3"], "Solves"],
+        table![C!["table table-sm table-dark"],
+            thead![
+                tr![
+                    th![ attrs! { At::Scope => "col", }, "Challenge" ],
+                    th![ C!["text-right"], attrs! { At::Scope => "col", }, "Points" ],
+                    th![ attrs! { At::Scope => "col", }, "Solved at" ],
+                ]
+            ],
+            tbody![
+                solves.map(|solve| {
+                    let (name, points) = match challenge(&solve.challenge_id) {
+                        Some(challenge) => (format!("{} - {}", challenge.letter, challenge.name), challenge.points.to_string()),
+                        None => (solve.challenge_id.clone(), "-".to_string()),
+                    };
+                    tr![
+                        td![ a![attrs! { At::Href => urls().challenge(&solve.challenge_id) }, name] ],
+                        td![ C!["text-right"], points ],
+                        td![ format_time(app, solve.solved_at) ],
+                    ]
+                }),
+            ],
+        ],
+        //
+        // Recent submissions
+        //
+        h4!["Recent submissions"],
+        match &model.team {
+            Some(details) => ul![C!["list-unstyled"],
+                details.recent_submissions.iter().take(RECENT_SUBMISSIONS).map(|submission| li![
+                    format!("{} ", format_time(app, submission.submitted_at)),
+                    match challenge(&submission.challenge_id) {
+                        Some(challenge) => challenge.letter.clone(),
+                        None => submission.challenge_id.clone(),
+                    },
+                    span![
+                        if submission.correct { " correct" } else { " incorrect" },
+                        style! {
+                            St::Color => if submission.correct { "#50fa7b" } else { "#ff5555" },
+                        }
+                    ],
+                ]),
+            ],
+            None => p!["Loading..."],
+        },
+        style! {
+            St::Color => "#FFFFFF",
+        }
+    ]
+}
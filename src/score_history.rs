@@ -0,0 +1,143 @@
+use crate::Id;
+use std::collections::HashMap;
+
+///
+/// Score of every team over time, all series share the same sample times.
+///
+pub struct ScoreHistory {
+    // Server time of each sample in ms since epoch
+    times: Vec<f64>,
+    series: HashMap<Id, Vec<f64>>,
+    // Oldest samples are dropped beyond this
+    capacity: usize,
+}
+
+impl ScoreHistory {
+    ///
+    /// Create an empty history.
+    ///
+    /// * `capacity` - Number of samples kept.
+    pub fn new(capacity: usize) -> ScoreHistory {
+        ScoreHistory {
+            times: Vec::new(),
+            series: HashMap::new(),
+            capacity,
+        }
+    }
+
+    ///
+    /// Record the current score of every team.
+    ///
+    /// Teams seen for the first time start at 0, teams missing from
+    /// `scores` keep their last score.
+    ///
+    /// * `at` - Server time of the sample.
+    /// * `scores` - Current score per team.
+    pub fn sample<'a>(&mut self, at: f64, scores: impl IntoIterator<Item = (&'a Id, i64)>) {
+        let len = self.times.len();
+        for (team_id, score) in scores {
+            let series = self
+                .series
+                .entry(team_id.clone())
+                .or_insert_with(|| vec![0.0; len]);
+            series.push(score as f64);
+        }
+        for series in self.series.values_mut() {
+            if series.len() == len {
+                let last = series.last().copied().unwrap_or(0.0);
+                series.push(last);
+            }
+        }
+        self.times.push(at);
+
+        if self.times.len() > self.capacity {
+            let excess = self.times.len() - self.capacity;
+            self.times.drain(..excess);
+            for series in self.series.values_mut() {
+                series.drain(..excess);
+            }
+        }
+    }
+
+    ///
+    /// Replace the samples with scores rebuilt from past solves.
+    ///
+    /// One sample at `start` with every team at 0, then one per solve.
+    ///
+    /// * `start` - Server time of the first sample.
+    /// * `teams` - Teams known before any solve.
+    /// * `solves` - Team, server time and points of each solve, oldest first.
+    pub fn rebuild<'a>(
+        &mut self,
+        start: f64,
+        teams: impl IntoIterator<Item = &'a Id>,
+        solves: impl IntoIterator<Item = (&'a Id, f64, i64)>,
+    ) {
+        self.clear();
+        let mut totals: HashMap<Id, i64> = teams.into_iter().map(|team_id| (team_id.clone(), 0)).collect();
+        self.sample(start, totals.iter().map(|(team_id, total)| (team_id, *total)));
+        for (team_id, at, points) in solves {
+            let total = {
+                let total = totals.entry(team_id.clone()).or_insert(0);
+                *total += points;
+                *total
+            };
+            self.sample(at, vec![(team_id, total)]);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.times.clear();
+        self.series.clear();
+    }
+
+    ///
+    /// Server time of each sample, oldest first.
+    ///
+    pub fn times(&self) -> &[f64] {
+        &self.times
+    }
+
+    pub fn last_sample_at(&self) -> Option<f64> {
+        self.times.last().copied()
+    }
+
+    ///
+    /// Scores of a team, one per sample time.
+    ///
+    /// * `team_id` - Team to get the series of.
+    pub fn series(&self, team_id: &str) -> Option<&[f64]> {
+        self.series.get(team_id).map(Vec::as_slice)
+    }
+
+    ///
+    /// Teams with the highest latest score, best first.
+    ///
+    /// * `count` - Maximum number of teams.
+    pub fn top_teams(&self, count: usize) -> Vec<&Id> {
+        let mut teams: Vec<(&Id, f64)> = self
+            .series
+            .iter()
+            .map(|(team_id, series)| (team_id, series.last().copied().unwrap_or(0.0)))
+            .collect();
+        teams.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap().then_with(|| a.0.cmp(b.0)));
+        teams.into_iter().take(count).map(|(team_id, _)| team_id).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rebuilds_totals_from_solves() {
+        let (a, b) = ("a".to_string(), "b".to_string());
+        let mut history = ScoreHistory::new(10);
+        history.sample(50.0, vec![(&a, 999)]);
+        history.rebuild(0.0, vec![&a, &b], vec![(&a, 10.0, 100), (&b, 20.0, 200), (&a, 30.0, 50)]);
+        assert_eq!(history.times(), &[0.0, 10.0, 20.0, 30.0]);
+        assert_eq!(history.series("a"), Some(&[0.0, 100.0, 100.0, 150.0][..]));
+        assert_eq!(history.series("b"), Some(&[0.0, 0.0, 200.0, 200.0][..]));
+        assert_eq!(history.top_teams(1), vec![&b]);
+    }
+}
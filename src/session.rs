@@ -0,0 +1,41 @@
+use seed::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// LocalStorage key of the signed in player
+pub const STORAGE_KEY: &str = "securethebox-session";
+
+/// Player signed in through the log in or sign up form.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Session {
+    pub user_id: String,
+    pub name: String,
+    /// Sent as a bearer token with every GraphQL request.
+    pub token: String,
+}
+
+impl Session {
+    ///
+    /// Load the session persisted in LocalStorage, `None` when signed out.
+    ///
+    pub fn load() -> Option<Session> {
+        LocalStorage::get(STORAGE_KEY).ok()
+    }
+
+    ///
+    /// Persist the session in LocalStorage.
+    ///
+    pub fn store(&self) {
+        if let Err(error) = LocalStorage::insert(STORAGE_KEY, self) {
+            seed::log!("Cannot store session", error);
+        }
+    }
+
+    ///
+    /// Forget the persisted session.
+    ///
+    pub fn clear() {
+        if let Err(error) = LocalStorage::remove(STORAGE_KEY) {
+            seed::log!("Cannot remove session", error);
+        }
+    }
+}
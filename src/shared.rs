@@ -1,4 +1,6 @@
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
 
 /// Message from the server to the client.
 #[derive(Serialize, Deserialize)]
@@ -37,10 +39,37 @@ pub struct Payload {
 }
 
 
+const MS_PER_SECOND: i64 = 1000;
+const MS_PER_MINUTE: i64 = 60 * MS_PER_SECOND;
+const MS_PER_HOUR: i64 = 60 * MS_PER_MINUTE;
+const MS_PER_DAY: i64 = 24 * MS_PER_HOUR;
+
+///
+/// Signed duration with day precision formatting.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Clock {
-    hours: i64,
-    minutes: i64,
-    seconds: i64,
+    ms: i64,
+}
+
+/// Error returned when a duration string cannot be parsed.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParseClockError {
+    Empty,
+    /// Not `[-][Nd ]hh:mm:ss` or an ISO 8601 duration.
+    InvalidFormat(String),
+    /// Minutes or seconds of `hh:mm:ss` are 60 or more, or the duration does not fit in milliseconds.
+    OutOfRange(String),
+}
+
+impl fmt::Display for ParseClockError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseClockError::Empty => write!(f, "empty duration"),
+            ParseClockError::InvalidFormat(s) => write!(f, "invalid duration: {}", s),
+            ParseClockError::OutOfRange(s) => write!(f, "duration field out of range: {}", s),
+        }
+    }
 }
 
 impl Clock {
@@ -49,35 +78,371 @@ impl Clock {
     /// Create a new clock.
     ///
     pub fn new() -> Clock {
-        Clock {
-            hours: 0,
-            minutes: 0,
-            seconds: 0,
-        }
+        Clock { ms: 0 }
+    }
+
+    ///
+    /// Create a clock from milliseconds.
+    ///
+    /// * `ms` - Milliseconds, negative for a duration in the past.
+    pub fn from_ms(ms: i64) -> Clock {
+        Clock { ms }
     }
 
     ///
     /// Set the clock time in milliseconds.
     ///
-    /// * `ms` - Milliseconds to set time from.
+    /// * `ms` - Milliseconds to set time from, may be negative.
     pub fn set_time_ms(&mut self, ms: i64) {
-        self.seconds = (ms / 1000) % 60;
-        self.minutes = (ms / (1000 * 60)) % 60;
-        self.hours = (ms / (1000 * 60 * 60)) % 24;
+        self.ms = ms;
     }
 
     ///
     /// Set the clock time in seconds.
     ///
-    /// * `seconds` - Seconds to set time from.
+    /// * `seconds` - Seconds to set time from, may be negative.
     pub fn set_time_secs(&mut self, seconds: i64) {
         self.set_time_ms(seconds * 1000);
     }
 
     ///
-    /// Get the clock time in hh:mm:ss notation.
+    /// Get the clock time in milliseconds.
+    ///
+    pub fn as_ms(&self) -> i64 {
+        self.ms
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.ms < 0
+    }
+
+    pub fn days(&self) -> i64 {
+        (self.abs_ms() / MS_PER_DAY as u64) as i64
+    }
+
+    pub fn hours(&self) -> i64 {
+        ((self.abs_ms() / MS_PER_HOUR as u64) % 24) as i64
+    }
+
+    pub fn minutes(&self) -> i64 {
+        ((self.abs_ms() / MS_PER_MINUTE as u64) % 60) as i64
+    }
+
+    pub fn seconds(&self) -> i64 {
+        ((self.abs_ms() / MS_PER_SECOND as u64) % 60) as i64
+    }
+
+    // `abs` overflows for `i64::MIN`, e.g. `-inf as i64`
+    fn abs_ms(&self) -> u64 {
+        self.ms.unsigned_abs()
+    }
+
+    fn sign(&self) -> &'static str {
+        if self.is_negative() { "-" } else { "" }
+    }
+
+    ///
+    /// Get the clock time in compact `[-][Nd ]hh:mm:ss` notation.
     ///
     pub fn get_time(&self) -> String {
-        format!("{:02}:{:02}:{:02}", self.hours, self.minutes, self.seconds)
+        // Less than a second shows as zero, without a sign
+        let sign = if self.abs_ms() < MS_PER_SECOND as u64 { "" } else { self.sign() };
+        if self.days() > 0 {
+            format!("{}{}d {:02}:{:02}:{:02}", sign, self.days(), self.hours(), self.minutes(), self.seconds())
+        } else {
+            format!("{}{:02}:{:02}:{:02}", sign, self.hours(), self.minutes(), self.seconds())
+        }
+    }
+
+    ///
+    /// Get the clock time spelled out, e.g. `2 days 1 hour 5 seconds`.
+    ///
+    pub fn get_time_verbose(&self) -> String {
+        let parts: Vec<String> = [
+            (self.days(), "day"),
+            (self.hours(), "hour"),
+            (self.minutes(), "minute"),
+            (self.seconds(), "second"),
+        ]
+        .iter()
+        .filter(|(value, _)| *value > 0)
+        .map(|(value, unit)| format!("{} {}{}", value, unit, if *value == 1 { "" } else { "s" }))
+        .collect();
+
+        if parts.is_empty() {
+            "0 seconds".to_string()
+        } else {
+            format!("{}{}", self.sign(), parts.join(" "))
+        }
+    }
+
+    ///
+    /// Get the clock time as ISO 8601 duration, e.g. `P2DT1H5S`.
+    ///
+    pub fn get_time_iso(&self) -> String {
+        let mut iso = format!("{}P", self.sign());
+        if self.days() > 0 {
+            iso.push_str(&format!("{}D", self.days()));
+        }
+        let millis = self.abs_ms() % MS_PER_SECOND as u64;
+        let seconds = if millis > 0 {
+            format!("{}.{:03}", self.seconds(), millis).trim_end_matches('0').to_string()
+        } else {
+            self.seconds().to_string()
+        };
+        let time: String = [
+            (self.hours().to_string(), "H"),
+            (self.minutes().to_string(), "M"),
+            (seconds, "S"),
+        ]
+        .iter()
+        .filter(|(value, _)| value != "0")
+        .map(|(value, unit)| format!("{}{}", value, unit))
+        .collect();
+        if !time.is_empty() {
+            iso.push_str(&format!("T{}", time));
+        } else if self.days() == 0 {
+            iso.push_str("T0S");
+        }
+        iso
+    }
+
+    ///
+    /// Parse `[-][Nd ]hh:mm:ss` or an ISO 8601 duration without years and months.
+    ///
+    /// * `s` - Duration string, e.g. `48:00:00`, `-00:05:00` or `P2DT4H`.
+    pub fn parse(s: &str) -> Result<Clock, ParseClockError> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(ParseClockError::Empty);
+        }
+        let (negative, rest) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        let ms = if rest.starts_with('P') || rest.starts_with('p') {
+            parse_iso(&rest[1..], s)?
+        } else {
+            parse_hms(rest, s)?
+        };
+        Ok(Clock { ms: if negative { -ms } else { ms } })
+    }
+}
+
+impl FromStr for Clock {
+    type Err = ParseClockError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Clock::parse(s)
+    }
+}
+
+impl fmt::Display for Clock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.get_time())
+    }
+}
+
+//
+// `[Nd ]hh:mm:ss`, hours are not limited to 24
+//
+fn parse_hms(s: &str, original: &str) -> Result<i64, ParseClockError> {
+    let invalid = || ParseClockError::InvalidFormat(original.to_string());
+    let (days, time) = match s.find('d') {
+        Some(index) => (
+            parse_digits(s[..index].trim()).ok_or_else(invalid)?,
+            s[index + 1..].trim(),
+        ),
+        None => (0, s),
+    };
+    let fields: Vec<i64> = time
+        .split(':')
+        .map(|field| parse_digits(field).ok_or_else(invalid))
+        .collect::<Result<_, _>>()?;
+    match fields.as_slice() {
+        [hours, minutes, seconds] => {
+            if *minutes >= 60 || *seconds >= 60 {
+                return Err(ParseClockError::OutOfRange(original.to_string()));
+            }
+            // Too many days or hours to fit in milliseconds
+            [(days, MS_PER_DAY), (*hours, MS_PER_HOUR), (*minutes, MS_PER_MINUTE), (*seconds, MS_PER_SECOND)]
+                .iter()
+                .try_fold(0i64, |ms, (value, unit)| value.checked_mul(*unit)?.checked_add(ms))
+                .ok_or_else(|| ParseClockError::OutOfRange(original.to_string()))
+        }
+        _ => Err(invalid()),
+    }
+}
+
+//
+// Digits only, `parse` would accept a sign
+//
+fn parse_digits(s: &str) -> Option<i64> {
+    if s.is_empty() || !s.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+// ISO 8601 units in the order they have to appear, each at most once,
+// with whether they belong after the `T`
+const ISO_UNITS: [(bool, char, i64); 5] = [
+    (false, 'W', 7 * MS_PER_DAY),
+    (false, 'D', MS_PER_DAY),
+    (true, 'H', MS_PER_HOUR),
+    (true, 'M', MS_PER_MINUTE),
+    (true, 'S', MS_PER_SECOND),
+];
+
+//
+// ISO 8601 duration after the `P`, e.g. `1W2DT3H4M5.5S`
+//
+fn parse_iso(s: &str, original: &str) -> Result<i64, ParseClockError> {
+    let invalid = || ParseClockError::InvalidFormat(original.to_string());
+    let mut ms = 0.0;
+    let mut in_time = false;
+    let mut number = String::new();
+    let mut next_unit = 0;
+    let mut has_value = false;
+    let mut has_time_value = false;
+    for c in s.chars() {
+        match c.to_ascii_uppercase() {
+            'T' if !in_time && number.is_empty() => in_time = true,
+            '0'..='9' | '.' | ',' => number.push(if c == ',' { '.' } else { c }),
+            // Years and months have no fixed length
+            unit => {
+                let value: f64 = number.parse().map_err(|_| invalid())?;
+                number.clear();
+                let index = ISO_UNITS[next_unit..]
+                    .iter()
+                    .position(|(time, iso_unit, _)| *time == in_time && *iso_unit == unit)
+                    .ok_or_else(invalid)?
+                    + next_unit;
+                next_unit = index + 1;
+                has_value = true;
+                has_time_value |= in_time;
+                ms += value * ISO_UNITS[index].2 as f64;
+            }
+        }
+    }
+    if !number.is_empty() || !has_value || (in_time && !has_time_value) {
+        return Err(invalid());
+    }
+    // Too long to fit in milliseconds
+    if ms >= i64::MAX as f64 {
+        return Err(ParseClockError::OutOfRange(original.to_string()));
+    }
+    Ok(ms.round() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_days_instead_of_wrapping() {
+        let clock = Clock::from_ms(2 * MS_PER_DAY + 3 * MS_PER_HOUR + 4 * MS_PER_MINUTE + 5 * MS_PER_SECOND);
+        assert_eq!(clock.get_time(), "2d 03:04:05");
+        assert_eq!(clock.get_time_verbose(), "2 days 3 hours 4 minutes 5 seconds");
+        assert_eq!(clock.get_time_iso(), "P2DT3H4M5S");
+    }
+
+    #[test]
+    fn formats_negative_durations() {
+        let clock = Clock::from_ms(-(5 * MS_PER_MINUTE));
+        assert!(clock.is_negative());
+        assert_eq!(clock.get_time(), "-00:05:00");
+        assert_eq!(clock.get_time_verbose(), "-5 minutes");
+        assert_eq!(clock.get_time_iso(), "-PT5M");
+    }
+
+    #[test]
+    fn formats_zero_and_singular_units() {
+        assert_eq!(Clock::new().get_time(), "00:00:00");
+        assert_eq!(Clock::new().get_time_verbose(), "0 seconds");
+        assert_eq!(Clock::new().get_time_iso(), "PT0S");
+        let clock = Clock::from_ms(MS_PER_DAY + MS_PER_SECOND);
+        assert_eq!(clock.get_time_verbose(), "1 day 1 second");
+        assert_eq!(Clock::from_ms(MS_PER_DAY).get_time_iso(), "P1D");
+        assert_eq!(Clock::from_ms(1500).get_time_iso(), "PT1.5S");
+        assert_eq!(Clock::from_ms(MS_PER_DAY + MS_PER_HOUR).get_time_iso(), "P1DT1H");
+    }
+
+    #[test]
+    fn set_time_secs_matches_set_time_ms() {
+        let mut clock = Clock::new();
+        clock.set_time_secs(-90);
+        assert_eq!(clock.as_ms(), -90_000);
+        assert_eq!(clock.get_time(), "-00:01:30");
+    }
+
+    #[test]
+    fn parses_hh_mm_ss() {
+        assert_eq!(Clock::parse("48:00:00").unwrap().get_time(), "2d 00:00:00");
+        assert_eq!(Clock::parse("-00:05:00").unwrap().as_ms(), -5 * MS_PER_MINUTE);
+        assert_eq!(Clock::parse("1d 02:03:04").unwrap(), Clock::parse("26:03:04").unwrap());
+        assert_eq!("01:02:03".parse::<Clock>().unwrap().as_ms(), MS_PER_HOUR + 2 * MS_PER_MINUTE + 3 * MS_PER_SECOND);
+    }
+
+    #[test]
+    fn parses_iso_durations() {
+        assert_eq!(Clock::parse("P2DT4H").unwrap().as_ms(), 2 * MS_PER_DAY + 4 * MS_PER_HOUR);
+        assert_eq!(Clock::parse("PT1.5S").unwrap().as_ms(), 1500);
+        assert_eq!(Clock::parse("P1W").unwrap().as_ms(), 7 * MS_PER_DAY);
+        assert_eq!(Clock::parse("-PT30M").unwrap().as_ms(), -30 * MS_PER_MINUTE);
+        assert_eq!(Clock::parse("PT0S").unwrap(), Clock::new());
+    }
+
+    #[test]
+    fn formats_round_trip_through_parse() {
+        for ms in [0, 1500, -90_000, 2 * MS_PER_DAY + 5 * MS_PER_SECOND, -(3 * MS_PER_DAY + MS_PER_HOUR)].iter() {
+            let clock = Clock::from_ms(*ms);
+            assert_eq!(Clock::parse(&clock.get_time_iso()).unwrap(), clock);
+            assert_eq!(Clock::parse(&clock.get_time()).unwrap().as_ms(), ms / 1000 * 1000);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_durations() {
+        assert_eq!(Clock::parse(""), Err(ParseClockError::Empty));
+        assert_eq!(Clock::parse("00:60:00"), Err(ParseClockError::OutOfRange("00:60:00".to_string())));
+        assert_eq!(
+            Clock::parse("9999999999999:00:00"),
+            Err(ParseClockError::OutOfRange("9999999999999:00:00".to_string()))
+        );
+        assert!(matches!(Clock::parse("999999999999d 00:00:00"), Err(ParseClockError::OutOfRange(_))));
+        assert!(Clock::parse("12:00").is_err());
+        assert!(Clock::parse("aa:bb:cc").is_err());
+        assert!(Clock::parse("P1Y").is_err());
+        assert!(Clock::parse("P").is_err());
+        assert!(Clock::parse("PT5").is_err());
+        assert!(Clock::parse("P1H").is_err());
+    }
+
+    #[test]
+    fn rejects_signed_days() {
+        assert!(Clock::parse("--5d 00:00:00").is_err());
+        assert!(Clock::parse("+-1d 00:00:00").is_err());
+        assert_eq!(Clock::parse("+5d 00:00:00").unwrap().as_ms(), 5 * MS_PER_DAY);
+    }
+
+    #[test]
+    fn rejects_malformed_iso_durations() {
+        assert!(matches!(Clock::parse("PT99999999999999999999H"), Err(ParseClockError::OutOfRange(_))));
+        assert!(Clock::parse("PT1H2H").is_err());
+        assert!(Clock::parse("PT1M2H").is_err());
+        assert!(Clock::parse("P1D2W").is_err());
+        assert!(Clock::parse("P1DT").is_err());
+        assert!(Clock::parse("PT").is_err());
+        assert_eq!(Clock::parse("P1W2DT3H4M5S").unwrap().as_ms(),
+            9 * MS_PER_DAY + 3 * MS_PER_HOUR + 4 * MS_PER_MINUTE + 5 * MS_PER_SECOND);
+    }
+
+    #[test]
+    fn formats_extreme_and_sub_second_durations() {
+        assert_eq!(Clock::from_ms(f64::NEG_INFINITY as i64).days(), (i64::MIN.unsigned_abs() / MS_PER_DAY as u64) as i64);
+        assert!(Clock::from_ms(i64::MIN).get_time().starts_with('-'));
+        assert_eq!(Clock::from_ms(-500).get_time(), "00:00:00");
+        assert_eq!(Clock::from_ms(-1500).get_time(), "-00:00:01");
     }
 }
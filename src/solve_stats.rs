@@ -0,0 +1,97 @@
+use crate::{Challenge, Id, Solve};
+use std::collections::HashSet;
+
+// Smallest histogram bin, avoids sub-minute bins early in the event
+const MIN_BIN_MS: f64 = 60.0 * 1000.0;
+
+/// Solve statistics of one challenge.
+pub struct ChallengeStats<'a> {
+    pub challenge: &'a Challenge,
+    pub solves: usize,
+    /// Fraction of the active teams that solved it, 0 to 1.
+    pub solve_rate: f64,
+    /// Median time from release to solve in ms.
+    pub median_time_to_solve: Option<f64>,
+    /// Number of solves per time-to-solve bin.
+    pub histogram: Vec<usize>,
+}
+
+/// Solve statistics of every challenge, histograms share the same bins.
+pub struct SolveStats<'a> {
+    pub challenges: Vec<ChallengeStats<'a>>,
+    /// Teams with at least one solve.
+    pub active_teams: usize,
+    /// Width of a histogram bin in ms.
+    pub bin_width: f64,
+}
+
+///
+/// Compute the solve statistics from the solves known to the client.
+///
+/// Time to solve is counted from the release of the challenge, or from
+/// the start of the event for challenges released with it.
+///
+/// * `challenges` - All challenges of the event.
+/// * `solves` - All solves of the event.
+/// * `starts_at` - Start of the event, the first solve if unknown.
+/// * `bins` - Number of histogram bins.
+pub fn compute<'a>(challenges: &'a [Challenge], solves: &[Solve], starts_at: Option<f64>, bins: usize) -> SolveStats<'a> {
+    let bins = bins.max(1);
+    let starts_at = starts_at
+        .or_else(|| solves.iter().map(|solve| solve.solved_at).reduce(f64::min))
+        .unwrap_or(0.0);
+    let active_teams = solves.iter().map(|solve| &solve.team_id).collect::<HashSet<&Id>>().len();
+
+    let times_to_solve: Vec<Vec<f64>> = challenges
+        .iter()
+        .map(|challenge| {
+            let released_at = challenge.releases_at.map_or(starts_at, |releases_at| releases_at.max(starts_at));
+            let mut times: Vec<f64> = solves
+                .iter()
+                .filter(|solve| solve.challenge_id == challenge.id)
+                .map(|solve| (solve.solved_at - released_at).max(0.0))
+                .collect();
+            times.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            times
+        })
+        .collect();
+
+    let longest = times_to_solve.iter().flatten().copied().fold(0.0, f64::max);
+    let bin_width = (longest / bins as f64).max(MIN_BIN_MS);
+
+    let challenges = challenges
+        .iter()
+        .zip(times_to_solve)
+        .map(|(challenge, times)| {
+            let mut histogram = vec![0; bins];
+            for time in &times {
+                histogram[((time / bin_width) as usize).min(bins - 1)] += 1;
+            }
+            ChallengeStats {
+                challenge,
+                solves: times.len(),
+                solve_rate: if active_teams == 0 { 0.0 } else { times.len() as f64 / active_teams as f64 },
+                median_time_to_solve: median(&times),
+                histogram,
+            }
+        })
+        .collect();
+
+    SolveStats {
+        challenges,
+        active_teams,
+        bin_width,
+    }
+}
+
+//
+// Median of sorted values
+//
+fn median(sorted: &[f64]) -> Option<f64> {
+    let middle = sorted.len() / 2;
+    match sorted.len() {
+        0 => None,
+        len if len % 2 == 1 => Some(sorted[middle]),
+        _ => Some((sorted[middle - 1] + sorted[middle]) / 2.0),
+    }
+}
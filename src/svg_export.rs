@@ -0,0 +1,146 @@
+use seed::prelude::*;
+
+const FOOTER_LINE_HEIGHT: f64 = 18.0;
+const BACKGROUND_COLOR: &str = "#282a36";
+const FOOTER_COLOR: &str = "#6272a4";
+
+///
+/// Serialize a chart into a self-contained SVG document.
+///
+/// The chart keeps its own title and legend, `metadata` is added below it
+/// on the background of the page.
+///
+/// * `chart` - Root `svg` node of the chart, it must have a `viewBox`.
+/// * `metadata` - Lines of the footer, e.g. event name and export time.
+pub fn standalone<Ms>(chart: &Node<Ms>, metadata: &[String]) -> String {
+    let (width, height) = match chart {
+        Node::Element(el) => el
+            .attrs
+            .vals
+            .get(&At::ViewBox)
+            .and_then(view_box_size)
+            .unwrap_or((800.0, 300.0)),
+        _ => (800.0, 300.0),
+    };
+    let total_height = height + FOOTER_LINE_HEIGHT * (metadata.len() as f64 + 0.5);
+
+    let mut svg = String::new();
+    svg.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    svg.push_str(&format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" font-family=\"sans-serif\">",
+        w = width,
+        h = total_height
+    ));
+    svg.push_str(&format!("<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>", BACKGROUND_COLOR));
+    // Nested `svg` fixes the size the chart scales to
+    if let Node::Element(el) = chart {
+        svg.push_str(&format!(
+            "<svg x=\"0\" y=\"0\" width=\"{}\" height=\"{}\"{}>",
+            width,
+            height,
+            attributes(el, &[At::Width, At::Height])
+        ));
+        for child in &el.children {
+            write_node(&mut svg, child);
+        }
+        svg.push_str("</svg>");
+    }
+    for (index, line) in metadata.iter().enumerate() {
+        svg.push_str(&format!(
+            "<text x=\"8\" y=\"{}\" fill=\"{}\" font-size=\"12\">{}</text>",
+            height + FOOTER_LINE_HEIGHT * (index as f64 + 1.0),
+            FOOTER_COLOR,
+            escape(line)
+        ));
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+///
+/// Let the browser save a document as a file.
+///
+/// * `file_name` - Suggested name of the file.
+/// * `svg` - Content of the file.
+pub fn download(file_name: &str, svg: &str) {
+    let href = format!(
+        "data:image/svg+xml;charset=utf-8,{}",
+        String::from(js_sys::encode_uri_component(svg))
+    );
+    let link = seed::document()
+        .create_element("a")
+        .ok()
+        .and_then(|link| link.dyn_into::<web_sys::HtmlElement>().ok());
+    match link {
+        Some(link) => {
+            link.set_attribute("href", &href).ok();
+            link.set_attribute("download", file_name).ok();
+            link.click();
+        }
+        None => seed::log!("Cannot create the download link"),
+    }
+}
+
+//
+// Width and height of a `viewBox="min-x min-y width height"`
+//
+fn view_box_size(view_box: &AtValue) -> Option<(f64, f64)> {
+    let view_box = match view_box {
+        AtValue::Some(view_box) => view_box,
+        _ => return None,
+    };
+    let numbers: Vec<f64> = view_box.split_whitespace().filter_map(|n| n.parse().ok()).collect();
+    match numbers.as_slice() {
+        [_, _, width, height] => Some((*width, *height)),
+        _ => None,
+    }
+}
+
+fn write_node<Ms>(svg: &mut String, node: &Node<Ms>) {
+    match node {
+        Node::Element(el) => {
+            let tag = el.tag.as_str();
+            svg.push_str(&format!("<{}{}", tag, attributes(el, &[])));
+            if el.children.is_empty() {
+                svg.push_str("/>");
+            } else {
+                svg.push('>');
+                for child in &el.children {
+                    write_node(svg, child);
+                }
+                svg.push_str(&format!("</{}>", tag));
+            }
+        }
+        Node::Text(text) => svg.push_str(&escape(&text.text)),
+        Node::Empty => {}
+    }
+}
+
+//
+// ` name="value"` pairs of an element, without the `skip`ped attributes
+//
+fn attributes<Ms>(el: &El<Ms>, skip: &[At]) -> String {
+    let mut attributes: String = el
+        .attrs
+        .vals
+        .iter()
+        .filter(|(name, _)| !skip.contains(name))
+        .filter_map(|(name, value)| match value {
+            AtValue::Ignored => None,
+            AtValue::None => Some(format!(" {}=\"\"", name.as_str())),
+            AtValue::Some(value) => Some(format!(" {}=\"{}\"", name.as_str(), escape(value))),
+        })
+        .collect();
+    let style = el.style.to_string();
+    if !style.is_empty() {
+        attributes.push_str(&format!(" style=\"{}\"", escape(&style)));
+    }
+    attributes
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
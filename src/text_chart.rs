@@ -0,0 +1,197 @@
+// Glyph of each series, in order
+pub const GLYPHS: [char; 8] = ['*', 'o', '+', 'x', '#', '@', '%', '&'];
+// Cell where several series meet
+pub const OVERLAP: char = '=';
+
+///
+/// Plot settings, mirrors `rasciigraph::Config`.
+///
+#[derive(Clone, Debug)]
+pub struct Config {
+    // Width of the y-axis labels
+    offset: usize,
+    // Number of rows above the lowest one
+    height: usize,
+    // Columns the series are resampled to, `None` keeps every value
+    width: Option<usize>,
+    caption: Option<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            offset: 3,
+            height: 10,
+            width: None,
+            caption: None,
+        }
+    }
+}
+
+impl Config {
+    pub fn with_offset(mut self, offset: usize) -> Config {
+        self.offset = offset;
+        self
+    }
+
+    pub fn with_height(mut self, height: usize) -> Config {
+        self.height = height.max(1);
+        self
+    }
+
+    pub fn with_width(mut self, width: usize) -> Config {
+        self.width = Some(width.max(1));
+        self
+    }
+
+    pub fn with_caption(mut self, caption: String) -> Config {
+        self.caption = Some(caption);
+        self
+    }
+}
+
+/// One named series of a text chart.
+pub struct Series {
+    pub name: String,
+    pub values: Vec<f64>,
+}
+
+///
+/// Plot several series on shared axes, one glyph per series.
+///
+/// The plot is followed by the caption and a legend. Cells shared by
+/// series show `OVERLAP`, a flat plot is a single row at its value.
+///
+/// * `series` - Series to plot, values are spaced evenly on the x-axis.
+/// * `config` - Plot settings.
+pub fn plot(series: &[Series], config: Config) -> String {
+    let columns: Vec<Vec<f64>> = series
+        .iter()
+        .map(|series| match config.width {
+            Some(width) => resample(&series.values, width),
+            None => series.values.clone(),
+        })
+        .collect();
+    let width = columns.iter().map(Vec::len).max().unwrap_or(0);
+    if width == 0 {
+        return String::new();
+    }
+
+    let values = columns.iter().flatten().copied();
+    let min = values.clone().fold(f64::INFINITY, f64::min);
+    let max = values.fold(f64::NEG_INFINITY, f64::max);
+    let range = max - min;
+    let height = if range > 0.0 { config.height } else { 0 };
+    let row_of = |value: f64| if height == 0 { 0 } else { ((max - value) / range * height as f64).round() as usize };
+
+    let mut grid = vec![vec![' '; width]; height + 1];
+    let mut overlapping = false;
+    for (index, values) in columns.iter().enumerate() {
+        let glyph = GLYPHS[index % GLYPHS.len()];
+        for (column, value) in values.iter().enumerate() {
+            let cell = &mut grid[row_of(*value).min(height)][column];
+            if *cell == ' ' || *cell == glyph {
+                *cell = glyph;
+            } else {
+                *cell = OVERLAP;
+                overlapping = true;
+            }
+        }
+    }
+
+    // Labels wider than the offset push the whole plot right
+    let labels: Vec<String> = (0..=height)
+        .map(|row| match height {
+            0 => format!("{:.2}", max),
+            _ => format!("{:.2}", max - row as f64 * range / height as f64),
+        })
+        .collect();
+    let offset = labels.iter().map(String::len).max().unwrap_or(0).max(config.offset);
+    let mut lines: Vec<String> = grid
+        .into_iter()
+        .zip(labels)
+        .enumerate()
+        .map(|(row, (cells, label))| {
+            let axis = if row == height { '┼' } else { '┤' };
+            format!(
+                "{:>offset$} {}{}",
+                label,
+                axis,
+                cells.into_iter().collect::<String>().trim_end(),
+                offset = offset
+            )
+        })
+        .collect();
+
+    let indent = " ".repeat(offset + 2);
+    if let Some(caption) = &config.caption {
+        lines.push(format!("{}{}", indent, caption));
+    }
+    let legend = series
+        .iter()
+        .enumerate()
+        .map(|(index, series)| format!("{} {}", GLYPHS[index % GLYPHS.len()], series.name))
+        .chain(if overlapping { Some(format!("{} several", OVERLAP)) } else { None })
+        .collect::<Vec<_>>()
+        .join("   ");
+    lines.push(format!("{}{}", indent, legend));
+    lines.join("\n")
+}
+
+//
+// Last value of each of `width` evenly sized buckets
+//
+fn resample(values: &[f64], width: usize) -> Vec<f64> {
+    if values.len() <= width {
+        return values.to_vec();
+    }
+    (1..=width)
+        .map(|bucket| values[bucket * values.len() / width - 1])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn series(name: &str, values: &[f64]) -> Series {
+        Series { name: name.to_string(), values: values.to_vec() }
+    }
+
+    #[test]
+    fn plots_a_flat_series_on_one_row_at_its_value() {
+        let plot = plot(&[series("a", &[5.0, 5.0, 5.0])], Config::default());
+        assert_eq!(plot, "5.00 ┼***\n      * a");
+    }
+
+    #[test]
+    fn labels_the_range_of_the_values() {
+        let plot = plot(&[series("a", &[0.0, 10.0])], Config::default().with_height(2));
+        let lines: Vec<&str> = plot.lines().collect();
+        assert_eq!(lines[0], "10.00 ┤ *");
+        assert_eq!(lines[1], " 5.00 ┤");
+        assert_eq!(lines[2], " 0.00 ┼*");
+    }
+
+    #[test]
+    fn marks_cells_shared_by_several_series() {
+        let plot = plot(
+            &[series("a", &[0.0, 10.0]), series("b", &[0.0, 5.0])],
+            Config::default().with_height(2),
+        );
+        let lines: Vec<&str> = plot.lines().collect();
+        assert_eq!(lines[2], " 0.00 ┼=");
+        assert_eq!(lines[3], "       * a   o b   = several");
+    }
+
+    #[test]
+    fn resamples_to_the_last_value_of_each_bucket() {
+        assert_eq!(resample(&[1.0, 2.0, 3.0, 4.0], 2), vec![2.0, 4.0]);
+        assert_eq!(resample(&[1.0, 2.0], 4), vec![1.0, 2.0]);
+    }
+
+    #[test]
+    fn plots_nothing_without_values() {
+        assert_eq!(plot(&[series("a", &[])], Config::default()), "");
+    }
+}
@@ -0,0 +1,60 @@
+// Number of samples kept to estimate the offset
+const MAX_SAMPLES: usize = 8;
+
+#[derive(Clone, Copy, Debug)]
+struct Sample {
+    round_trip: f64,
+    offset: f64,
+}
+
+///
+/// Estimate of the difference between the server clock and the local clock.
+///
+/// Every sample assumes the server read its clock half way through the
+/// request, the sample with the shortest round trip is the most accurate.
+pub struct TimeSync {
+    samples: Vec<Sample>,
+}
+
+impl TimeSync {
+    ///
+    /// Create a time sync without samples, the offset is 0.
+    ///
+    pub fn new() -> TimeSync {
+        TimeSync { samples: Vec::new() }
+    }
+
+    ///
+    /// Add a measurement, all times in ms since epoch.
+    ///
+    /// * `sent_at` - Local time the request was sent.
+    /// * `received_at` - Local time the response arrived.
+    /// * `server_time` - Server time contained in the response.
+    pub fn add_sample(&mut self, sent_at: f64, received_at: f64, server_time: f64) {
+        let round_trip = (received_at - sent_at).max(0.0);
+        let offset = server_time - (sent_at + round_trip / 2.0);
+        if self.samples.len() == MAX_SAMPLES {
+            self.samples.remove(0);
+        }
+        self.samples.push(Sample { round_trip, offset });
+    }
+
+    ///
+    /// Milliseconds to add to the local clock to get the server clock.
+    ///
+    pub fn offset(&self) -> f64 {
+        self.samples
+            .iter()
+            .min_by(|a, b| a.round_trip.partial_cmp(&b.round_trip).unwrap())
+            .map(|sample| sample.offset)
+            .unwrap_or(0.0)
+    }
+
+    ///
+    /// Convert a local time to server time, both in ms since epoch.
+    ///
+    /// * `local` - Local time, e.g. `js_sys::Date::now()`.
+    pub fn server_time(&self, local: f64) -> f64 {
+        local + self.offset()
+    }
+}
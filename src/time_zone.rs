@@ -0,0 +1,115 @@
+use seed::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// LocalStorage key of the viewer's choice
+pub const STORAGE_KEY: &str = "securethebox-time-zone";
+
+/// Time zone absolute times are displayed in.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum TimeZoneChoice {
+    /// The viewer's browser time zone.
+    Local,
+    Utc,
+    /// The official time zone of the event.
+    Event,
+}
+
+impl Default for TimeZoneChoice {
+    fn default() -> Self {
+        TimeZoneChoice::Local
+    }
+}
+
+impl TimeZoneChoice {
+    pub const ALL: [TimeZoneChoice; 3] = [TimeZoneChoice::Local, TimeZoneChoice::Utc, TimeZoneChoice::Event];
+
+    ///
+    /// Load the choice persisted in LocalStorage.
+    ///
+    pub fn load() -> TimeZoneChoice {
+        LocalStorage::get(STORAGE_KEY).unwrap_or_default()
+    }
+
+    ///
+    /// Persist the choice in LocalStorage.
+    ///
+    pub fn store(self) {
+        if let Err(error) = LocalStorage::insert(STORAGE_KEY, &self) {
+            seed::log!("Cannot store time zone", error);
+        }
+    }
+
+    pub fn key(self) -> &'static str {
+        match self {
+            TimeZoneChoice::Local => "local",
+            TimeZoneChoice::Utc => "utc",
+            TimeZoneChoice::Event => "event",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<TimeZoneChoice> {
+        TimeZoneChoice::ALL.iter().copied().find(|choice| choice.key() == key)
+    }
+
+    ///
+    /// IANA name of the zone, `None` for the browser zone.
+    ///
+    /// * `event_time_zone` - Official time zone of the event, if known.
+    pub fn iana_name(self, event_time_zone: Option<&str>) -> Option<String> {
+        match self {
+            TimeZoneChoice::Local => None,
+            TimeZoneChoice::Utc => Some("UTC".to_string()),
+            TimeZoneChoice::Event => event_time_zone.map(str::to_string),
+        }
+    }
+}
+
+///
+/// Format an absolute time in the given zone, e.g. `10/18/2026, 14:05:00 UTC`.
+///
+/// Falls back to the browser zone if the zone name is unknown.
+///
+/// * `ms` - Time in ms since epoch.
+/// * `time_zone` - IANA zone name, `None` for the browser zone.
+/// * `time_only` - Leave out the date.
+pub fn format(ms: f64, time_zone: Option<&str>, time_only: bool) -> String {
+    let date = js_sys::Date::new(&JsValue::from_f64(ms));
+    date_time_format(time_zone, time_only)
+        .or_else(|| date_time_format(None, time_only))
+        .and_then(|format| format.format().call1(&JsValue::NULL, &date).ok())
+        .and_then(|formatted| formatted.as_string())
+        .unwrap_or_else(|| String::from(date.to_locale_string("default", &JsValue::UNDEFINED)))
+}
+
+//
+// `new Intl.DateTimeFormat(...)` throws a RangeError for unknown zones,
+// `Reflect.construct` turns it into an `Err`
+//
+fn date_time_format(time_zone: Option<&str>, time_only: bool) -> Option<js_sys::Intl::DateTimeFormat> {
+    let options = js_sys::Object::new();
+    let set = |key: &str, value: &str| {
+        js_sys::Reflect::set(&options, &key.into(), &value.into()).ok();
+    };
+    if !time_only {
+        set("year", "numeric");
+        set("month", "2-digit");
+        set("day", "2-digit");
+    }
+    set("hour", "2-digit");
+    set("minute", "2-digit");
+    set("second", "2-digit");
+    set("timeZoneName", "short");
+    if let Some(time_zone) = time_zone {
+        set("timeZone", time_zone);
+    }
+
+    let intl = js_sys::Reflect::get(&js_sys::global(), &"Intl".into()).ok()?;
+    let constructor: js_sys::Function = js_sys::Reflect::get(&intl, &"DateTimeFormat".into())
+        .ok()?
+        .dyn_into()
+        .ok()?;
+    let arguments = js_sys::Array::of2(&JsValue::UNDEFINED, &options);
+    js_sys::Reflect::construct(&constructor, &arguments)
+        .ok()
+        .map(JsCast::unchecked_into)
+}